@@ -0,0 +1,94 @@
+//! This example shows how to fire bursts from gameplay events, such as collisions reported by a physics crate.
+//!
+//! Clicking stands in for a collision here: the emitter bursts more particles the closer the click is to it.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*, window::PrimaryWindow};
+use bevy_color::palettes::basic::*;
+use bevy_particle_systems::{
+    ParticleBurstRequest, ParticleSystem, ParticleSystemBundle, ParticleSystemPlugin, Playing,
+    VelocityModifier,
+};
+
+/// Stands in for the collision event of a physics crate.
+#[derive(Event)]
+struct Collision {
+    emitter: Entity,
+    strength: f32,
+}
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::BLACK))
+        .add_plugins((DefaultPlugins, ParticleSystemPlugin))
+        .add_event::<Collision>()
+        .add_systems(Startup, setup)
+        .add_systems(
+            Update,
+            (
+                collide_on_click.run_if(input_just_pressed(MouseButton::Left)),
+                burst_on_collision,
+            )
+                .chain(),
+        )
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+
+    // A looping system with no spawn rate only emits the bursts it is asked for.
+    commands.spawn((
+        ParticleSystemBundle {
+            particle_system: ParticleSystem {
+                spawn_rate_per_second: 0.0.into(),
+                max_particles: 5_000,
+                initial_speed: (100.0..400.0).into(),
+                lifetime: (0.5..1.5).into(),
+                scale: 2.0.into(),
+                velocity_modifiers: vec![VelocityModifier::Drag(0.01.into())],
+                color: (YELLOW.into()..Color::srgba(1.0, 0.0, 0.0, 0.0)).into(),
+                ..ParticleSystem::default()
+            },
+            ..default()
+        },
+        Playing,
+    ));
+}
+
+fn collide_on_click(
+    window: Query<&Window, With<PrimaryWindow>>,
+    camera: Query<(&Camera, &GlobalTransform)>,
+    emitters: Query<(Entity, &GlobalTransform), With<ParticleSystem>>,
+    mut collisions: EventWriter<Collision>,
+) {
+    let (camera, camera_transform) = camera.single();
+    let Some(click_position) = window
+        .single()
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world(camera_transform, cursor))
+        .map(|ray| ray.origin.truncate())
+    else {
+        return;
+    };
+
+    for (emitter, transform) in &emitters {
+        let distance = transform.translation().truncate().distance(click_position);
+        collisions.send(Collision {
+            emitter,
+            strength: (1.0 - distance / 500.0).max(0.1),
+        });
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn burst_on_collision(
+    mut collisions: EventReader<Collision>,
+    mut burst_requests: EventWriter<ParticleBurstRequest>,
+) {
+    for collision in collisions.read() {
+        burst_requests.send(ParticleBurstRequest {
+            entity: collision.emitter,
+            count: (collision.strength * 500.0) as usize,
+        });
+    }
+}
@@ -7,8 +7,8 @@ use bevy::{
 use bevy_app::PluginGroup;
 use bevy_asset::AssetServer;
 use bevy_particle_systems::{
-    CircleSegment, JitteredValue, ParticleSystem, ParticleSystemBundle, ParticleSystemPlugin,
-    Playing,
+    Angle, CircleSegment, JitteredValue, ParticleSystem, ParticleSystemBundle,
+    ParticleSystemPlugin, Playing,
 };
 
 fn main() {
@@ -49,7 +49,7 @@ fn startup_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                 looping: true,
                 scale: 0.07.into(),
                 system_duration_seconds: 5.0,
-                initial_rotation: (-90.0_f32).to_radians().into(),
+                initial_rotation: Angle::degrees(-90.0).into(),
                 rotate_to_movement_direction: true,
                 ..ParticleSystem::default()
             },
@@ -0,0 +1,82 @@
+//! This example strikes lightning bolts by connecting consecutive particles with line segments.
+//!
+//! Each bolt is an emitter darting downwards with a jittery sideways motion. Its particles stay where they spawn,
+//! and [`ConnectParticles`] joins each of them to the previous one, leaving a jagged path that fades out.
+use bevy::{prelude::*, DefaultPlugins};
+use bevy_asset::AssetServer;
+use bevy_color::palettes::basic::*;
+use bevy_particle_systems::{
+    ColorOverTime, ConnectParticles, JitteredValue, ParticleSystem, ParticleSystemBundle,
+    ParticleSystemPlugin, Playing,
+};
+use rand::Rng;
+
+#[derive(Resource)]
+struct Strikes(Timer);
+
+#[derive(Component)]
+struct Bolt;
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::BLACK))
+        .insert_resource(Strikes(Timer::from_seconds(0.8, TimerMode::Repeating)))
+        .add_plugins((DefaultPlugins, ParticleSystemPlugin)) // <-- Add the plugin
+        .add_systems(Startup, startup_system)
+        .add_systems(Update, (strike, move_bolts))
+        .run();
+}
+
+fn startup_system(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+}
+
+fn strike(
+    mut commands: Commands,
+    mut strikes: ResMut<Strikes>,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+) {
+    if !strikes.0.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let x = rand::thread_rng().gen_range(-300.0..300.0);
+    commands
+        .spawn(ParticleSystemBundle {
+            transform: Transform::from_xyz(x, 350.0, 0.0),
+            particle_system: ParticleSystem {
+                max_particles: 200,
+                texture: asset_server.load("px.png").into(),
+                spawn_rate_per_second: 120.0.into(),
+                initial_speed: 0.0.into(),
+                lifetime: JitteredValue::jittered(0.4, -0.05..0.05),
+                color: ColorOverTime::gradient([
+                    (0.0, WHITE),
+                    (1.0, Srgba::new(0.4, 0.6, 1.0, 0.0)),
+                ]),
+                scale: 3.0.into(),
+                connect_particles: Some(ConnectParticles {
+                    color: ColorOverTime::gradient([
+                        (0.0, WHITE),
+                        (0.3, Srgba::new(0.6, 0.8, 1.0, 1.0)),
+                        (1.0, Srgba::new(0.2, 0.3, 1.0, 0.0)),
+                    ]),
+                    width: 3.0,
+                }),
+                system_duration_seconds: 0.6,
+                despawn_on_finish: true,
+                ..ParticleSystem::default()
+            },
+            ..ParticleSystemBundle::default()
+        })
+        .insert((Bolt, Playing));
+}
+
+fn move_bolts(mut bolts: Query<&mut Transform, With<Bolt>>, time: Res<Time>) {
+    let mut rng = rand::thread_rng();
+    for mut transform in &mut bolts {
+        transform.translation.y -= 1200.0 * time.delta_seconds();
+        transform.translation.x += rng.gen_range(-12.0..12.0);
+    }
+}
@@ -50,10 +50,7 @@ fn spawn_particle_systems(
                         VelocityModifier::Vector(Vec3::new(0.0, -400.0, 0.0).into()),
                     ],
                     color: (BLUE.into()..Color::srgba(1.0, 0.0, 0.0, 0.0)).into(),
-                    bursts: vec![ParticleBurst {
-                        time: 0.0,
-                        count: 1000,
-                    }],
+                    bursts: vec![ParticleBurst::new(0.0, 1000)],
                     ..ParticleSystem::oneshot()
                 },
                 ..default()
@@ -0,0 +1,168 @@
+//! This example edits a running particle system purely through Bevy's reflection, the same way runtime inspectors such
+//! as `bevy-inspector-egui` do.
+//!
+//! Every two seconds, one of the following edits is applied to the [`ParticleSystem`]:
+//! - The emitter shape is switched to a `Line`, built from the registered `ReflectDefault` of [`Line`].
+//! - The emitter shape is switched back to a `CircleSegment`, built the same way.
+//! - A default velocity modifier is pushed to ``velocity_modifiers``, switched to the `Vector` variant and pointed down.
+//! - The velocity modifier is removed again.
+//!
+//! To tweak systems by hand instead, add `bevy-inspector-egui` to your app and add its `WorldInspectorPlugin`.
+//! [`ParticleSystemPlugin`] registers every type used by [`ParticleSystem`], so all of its fields can be edited:
+//!
+//! ```ignore
+//! App::new()
+//!     .add_plugins((DefaultPlugins, ParticleSystemPlugin))
+//!     .add_plugins(bevy_inspector_egui::quick::WorldInspectorPlugin::new())
+//!     .run();
+//! ```
+//!
+//! This crate doesn't depend on `bevy-inspector-egui`, not even as a dev-dependency, so this example sticks to
+//! the reflection calls the inspector is built on.
+
+use std::any::TypeId;
+
+use bevy::{
+    ecs::reflect::{AppTypeRegistry, ReflectComponent},
+    math::Vec3,
+    prelude::{App, Camera2dBundle, Commands, Entity, Res, Resource, With, World},
+    reflect::{
+        std_traits::ReflectDefault, DynamicEnum, DynamicTuple, GetPath, Reflect, ReflectMut,
+        TypeRegistry,
+    },
+    time::{Time, Timer, TimerMode},
+    DefaultPlugins,
+};
+use bevy_app::{Startup, Update};
+use bevy_asset::AssetServer;
+use bevy_color::palettes::basic::*;
+
+use bevy_particle_systems::{
+    CircleSegment, ColorOverTime, JitteredValue, Line, ParticleSystem, ParticleSystemBundle,
+    ParticleSystemPlugin, Playing, VectorOverTime, VelocityModifier,
+};
+
+#[derive(Resource)]
+struct ReflectionEdits {
+    timer: Timer,
+    step: usize,
+}
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, ParticleSystemPlugin)) // <-- Add the plugin
+        .insert_resource(ReflectionEdits {
+            timer: Timer::from_seconds(2.0, TimerMode::Repeating),
+            step: 0,
+        })
+        .add_systems(Startup, startup_system)
+        .add_systems(Update, edit_through_reflection)
+        .run();
+}
+
+fn startup_system(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle::default());
+
+    commands
+        .spawn(ParticleSystemBundle {
+            particle_system: ParticleSystem {
+                max_particles: 5_000,
+                texture: asset_server.load("px.png").into(),
+                spawn_rate_per_second: 500.0.into(),
+                initial_speed: JitteredValue::jittered(150.0, -25.0..25.0),
+                lifetime: JitteredValue::jittered(2.0, -0.5..0.5),
+                color: ColorOverTime::Constant(AQUA.into()),
+                scale: 3.0.into(),
+                looping: true,
+                ..ParticleSystem::default()
+            },
+            ..ParticleSystemBundle::default()
+        })
+        .insert(Playing);
+}
+
+fn edit_through_reflection(world: &mut World) {
+    let delta = world.resource::<Time>().delta();
+    let mut edits = world.resource_mut::<ReflectionEdits>();
+    if !edits.timer.tick(delta).just_finished() {
+        return;
+    }
+    let step = edits.step;
+    edits.step += 1;
+
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let reflect_component = registry
+        .get_type_data::<ReflectComponent>(TypeId::of::<ParticleSystem>())
+        .expect("ParticleSystem is registered by ParticleSystemPlugin");
+
+    let systems: Vec<Entity> = world
+        .query_filtered::<Entity, With<ParticleSystem>>()
+        .iter(world)
+        .collect();
+    for entity in systems {
+        let mut entity_mut = world.entity_mut(entity);
+        let Some(mut particle_system) = reflect_component.reflect_mut(&mut entity_mut) else {
+            continue;
+        };
+        let particle_system = particle_system.as_reflect_mut();
+
+        match step % 4 {
+            0 => {
+                let shape = particle_system.reflect_path_mut("emitter_shape").unwrap();
+                set_variant(shape, "Line", default_of::<Line>(&registry));
+            }
+            1 => {
+                let shape = particle_system.reflect_path_mut("emitter_shape").unwrap();
+                set_variant(
+                    shape,
+                    "CircleSegment",
+                    default_of::<CircleSegment>(&registry),
+                );
+            }
+            2 => {
+                let ReflectMut::List(modifiers) = particle_system
+                    .reflect_path_mut("velocity_modifiers")
+                    .unwrap()
+                    .reflect_mut()
+                else {
+                    unreachable!("velocity_modifiers is a Vec");
+                };
+                modifiers.push(default_of::<VelocityModifier>(&registry));
+
+                let modifier = modifiers.get_mut(modifiers.len() - 1).unwrap();
+                set_variant(modifier, "Vector", default_of::<VectorOverTime>(&registry));
+                modifier
+                    .reflect_path_mut(".0.0")
+                    .unwrap()
+                    .apply(&Vec3::new(0.0, -300.0, 0.0));
+            }
+            _ => {
+                let ReflectMut::List(modifiers) = particle_system
+                    .reflect_path_mut("velocity_modifiers")
+                    .unwrap()
+                    .reflect_mut()
+                else {
+                    unreachable!("velocity_modifiers is a Vec");
+                };
+                modifiers.pop();
+            }
+        }
+    }
+}
+
+/// Builds the default value of `T` from its registered `ReflectDefault`, as an inspector does for a type it only
+/// knows through the registry.
+fn default_of<T: Reflect>(registry: &TypeRegistry) -> Box<dyn Reflect> {
+    registry
+        .get_type_data::<ReflectDefault>(TypeId::of::<T>())
+        .expect("the type registers ReflectDefault")
+        .default()
+}
+
+/// Switches a reflected enum to a single field tuple variant.
+fn set_variant(value: &mut dyn Reflect, variant: &str, field: Box<dyn Reflect>) {
+    let mut fields = DynamicTuple::default();
+    fields.insert_boxed(field);
+    value.apply(&DynamicEnum::new(variant, fields));
+}
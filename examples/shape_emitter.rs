@@ -9,8 +9,8 @@ use bevy_asset::AssetServer;
 use bevy_color::palettes::basic::*;
 
 use bevy_particle_systems::{
-    CircleSegment, ColorOverTime, Curve, CurvePoint, EmitterShape, JitteredValue, ParticleSystem,
-    ParticleSystemBundle, ParticleSystemPlugin, Playing,
+    Angle, CircleSegment, ColorOverTime, Curve, CurvePoint, EmitterShape, JitteredValue,
+    ParticleSystem, ParticleSystemBundle, ParticleSystemPlugin, Playing,
 };
 
 fn main() {
@@ -52,7 +52,7 @@ fn startup_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                 emitter_shape: EmitterShape::line(200.0, std::f32::consts::FRAC_PI_4),
                 looping: true,
                 rotate_to_movement_direction: true,
-                initial_rotation: (-90.0_f32).to_radians().into(),
+                initial_rotation: Angle::degrees(-90.0).into(),
                 system_duration_seconds: 10.0,
                 max_distance: Some(300.0),
                 scale: 0.07.into(),
@@ -84,7 +84,7 @@ fn startup_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                 .into(),
                 looping: true,
                 rotate_to_movement_direction: true,
-                initial_rotation: (-90.0_f32).to_radians().into(),
+                initial_rotation: Angle::degrees(-90.0).into(),
                 system_duration_seconds: 10.0,
                 max_distance: Some(300.0),
                 scale: 0.07.into(),
@@ -12,8 +12,8 @@ use bevy_app::{Startup, Update};
 use bevy_asset::AssetServer;
 use bevy_color::palettes::basic::*;
 use bevy_particle_systems::{
-    CircleSegment, ColorOverTime, Curve, CurvePoint, JitteredValue, ParticleSpace, ParticleSystem,
-    ParticleSystemBundle, ParticleSystemPlugin, Playing,
+    CircleSegment, ColorOverTime, Curve, CurvePoint, InitialRotation, JitteredValue, ParticleSpace,
+    ParticleSystem, ParticleSystemBundle, ParticleSystemPlugin, Playing,
 };
 use bevy_time::{Time, Virtual};
 fn main() {
@@ -49,7 +49,7 @@ fn startup_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                 system_duration_seconds: 10.0,
                 space: ParticleSpace::World,
                 scale: 5.0.into(),
-                initial_rotation: JitteredValue::jittered(0.0, -2.0..2.0),
+                initial_rotation: InitialRotation::symmetric(2.0),
                 use_scaled_time: false,
                 ..ParticleSystem::default()
             },
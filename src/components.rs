@@ -1,29 +1,45 @@
 //! Defines bevy Components used by the particle system.
 
+use std::{ops::Range, sync::Arc};
+
 use bevy_asset::Handle;
+use bevy_color::Color;
 use bevy_ecs::prelude::{Bundle, Component, Entity, ReflectComponent};
+use bevy_log::warn;
 use bevy_math::{Vec2, Vec3};
 use bevy_reflect::prelude::*;
 use bevy_render::prelude::{Image, VisibilityBundle};
 use bevy_sprite::TextureAtlasLayout;
 use bevy_transform::prelude::{GlobalTransform, Transform};
+use rand::Rng;
 
 use crate::{
-    values::{ColorOverTime, JitteredValue, ValueOverTime},
-    AtlasIndex, EmitterShape, VelocityModifier,
+    values::{
+        AlphaOverTime, ColorBlendOp, ColorDriver, ColorOverTime, Curve, InitialRotation,
+        InitialRotationMode, JitteredValue, LoopCount, RandomValue, ValueOverTime, VectorOverTime,
+    },
+    AtlasIndex, EmissionControl, EmitterShape, FlickerModifier, Noise2D, VelocityConstraint,
+    VelocityModifier,
 };
 
 /// Defines a burst of a specified number of particles at the given time in a running particle system.
 ///
 /// Bursts do not count as part of the per-second spawn rate.
-#[derive(Debug, Clone, Copy, Reflect)]
+#[derive(Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Default)]
 pub struct ParticleBurst {
     /// The time during the life cycle of a system that the burst should occur.
     ///
     /// This value should be strictly less than the particle systems ``system_duration_seconds`` or it will
-    /// not fire.
+    /// not fire. When ``relative`` is set, this is a fraction of ``system_duration_seconds`` instead.
     pub time: f32,
 
+    /// Whether ``time`` is a fraction of the system's ``system_duration_seconds`` rather than seconds.
+    ///
+    /// Relative bursts are resolved against the current duration every frame, so they stay proportional when the
+    /// duration changes. See [`ParticleBurst::at_fraction`].
+    pub relative: bool,
+
     /// The number of particles to fire at the specified time.
     ///
     /// All particles in a burst are not counted towards the spawn rate, but are counted towards the system maximum.
@@ -34,21 +50,224 @@ pub struct ParticleBurst {
 impl ParticleBurst {
     /// Creates a new [`ParticleBurst`] at a specified time of the given number of particles.
     pub fn new(time: f32, count: usize) -> Self {
-        Self { time, count }
+        Self {
+            time,
+            count,
+            relative: false,
+        }
+    }
+
+    /// Creates a new [`ParticleBurst`] at a fraction, between `0.0` and `1.0`, of the system's
+    /// ``system_duration_seconds``.
+    pub fn at_fraction(fraction: f32, count: usize) -> Self {
+        Self {
+            time: fraction,
+            count,
+            relative: true,
+        }
+    }
+
+    /// The time in seconds at which this burst fires, for a system with the given duration.
+    pub fn resolved_time(&self, system_duration_seconds: f32) -> f32 {
+        if self.relative {
+            self.time * system_duration_seconds
+        } else {
+            self.time
+        }
     }
 }
 
-/// Defines what space a particle should operate in.
+/// Fires a burst of particles at a fixed cadence for as long as a system emits, like a machine gun's muzzle flashes.
+///
+/// This is a shorthand for a long list of evenly spaced [`ParticleBurst`]s, and fires alongside them.
+#[derive(Debug, Clone, Copy, Reflect)]
+#[reflect(Default)]
+pub struct RepeatingBurst {
+    /// The time, in seconds, between two bursts. The first burst fires when the system starts.
+    ///
+    /// An interval of `0.0` or less disables the repeating burst.
+    pub interval: f32,
+
+    /// The number of particles fired by each burst.
+    pub count: usize,
+
+    /// A random amount, up to this value, added to or removed from ``count`` for each burst.
+    pub jitter: usize,
+}
+
+impl RepeatingBurst {
+    /// Creates a new [`RepeatingBurst`] firing ``count`` particles every ``interval`` seconds.
+    pub fn new(interval: f32, count: usize) -> Self {
+        Self {
+            interval,
+            count,
+            jitter: 0,
+        }
+    }
+
+    /// Picks the number of particles for a single burst, applying ``jitter``.
+    pub fn get_count<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        if self.jitter == 0 {
+            return self.count;
+        }
+        rng.gen_range(self.count.saturating_sub(self.jitter)..=self.count + self.jitter)
+    }
+}
+
+impl Default for RepeatingBurst {
+    fn default() -> Self {
+        Self::new(0.1, 1)
+    }
+}
+
+/// Configures despawning particles once they leave the view of every active camera.
+#[derive(Debug, Clone, Copy, Reflect)]
+#[reflect(Default)]
+pub struct OffscreenDespawn {
+    /// Extra distance, in world units, around the visible area in which particles are still considered on screen.
+    pub margin: f32,
+
+    /// How long, in seconds, a newly spawned particle is kept alive regardless of whether it is on screen.
+    ///
+    /// This gives particles spawned outside of the view a chance to move into it before being culled.
+    pub grace_period: f32,
+}
+
+impl Default for OffscreenDespawn {
+    fn default() -> Self {
+        Self {
+            margin: 0.0,
+            grace_period: 0.5,
+        }
+    }
+}
+
+/// A volume particles are confined to, see [`ParticleSystem::despawn_region`].
+///
+/// The region is expressed in the same space as the particles' [`Transform`]:
+/// relative to the emitter for [`ParticleSpace::Local`] and in world coordinates otherwise.
 #[derive(Debug, Clone, Copy, Reflect)]
+#[reflect(Default)]
+pub enum ParticleRegion {
+    /// An axis aligned box between two corners.
+    Aabb {
+        /// The corner of the box with the smallest coordinates.
+        min: Vec3,
+        /// The corner of the box with the largest coordinates.
+        max: Vec3,
+    },
+    /// A sphere around a point.
+    Sphere {
+        /// The center of the sphere.
+        center: Vec3,
+        /// The radius of the sphere.
+        radius: f32,
+    },
+}
+
+impl Default for ParticleRegion {
+    fn default() -> Self {
+        Self::Sphere {
+            center: Vec3::ZERO,
+            radius: 1.0,
+        }
+    }
+}
+
+impl ParticleRegion {
+    /// Whether the point lies inside the region, boundary included.
+    pub fn contains(&self, point: Vec3) -> bool {
+        match *self {
+            Self::Aabb { min, max } => point.cmpge(min).all() && point.cmple(max).all(),
+            Self::Sphere { center, radius } => point.distance_squared(center) <= radius * radius,
+        }
+    }
+}
+
+/// Draws a slightly larger, tinted copy of each particle behind it, giving the particle an outline.
+///
+/// Each outline is a companion sprite entity spawned as a child of its particle, so it follows the particle's
+/// transform and is despawned with it. This doubles the number of sprite entities the system creates, along with
+/// the work done to extract and draw them, so prefer it for small numbers of particles that need to stand out.
+#[derive(Debug, Clone, Copy, Reflect)]
+#[reflect(Default)]
+pub struct ParticleOutline {
+    /// The color of the outline.
+    ///
+    /// The alpha of the outline is multiplied by the alpha of its particle every frame, so it fades out along with it.
+    pub color: Color,
+
+    /// How much larger than the particle the outline is, as a fraction of the particle's size.
+    ///
+    /// A thickness of `0.2` draws the outline at `1.2` times the size of the particle.
+    pub thickness: f32,
+}
+
+impl Default for ParticleOutline {
+    fn default() -> Self {
+        Self {
+            color: Color::BLACK,
+            thickness: 0.2,
+        }
+    }
+}
+
+/// Connects consecutive particles of a system with line segments, forming a jagged path such as a lightning bolt.
+///
+/// Each particle is connected to the particle the system spawned right before it. Each segment is a sprite entity
+/// stretched between its two particles, which follows them as they move and is despawned as soon as either of them
+/// is, breaking the path in two. Unlike a trail, the path runs across particles instead of following a single one.
+#[derive(Debug, Clone, Reflect)]
+#[reflect(Default)]
+pub struct ConnectParticles {
+    /// The color of the segments, sampled along the lifetime of the older particle of each segment.
+    ///
+    /// The particle's [`ParentTint`] is applied on top of it.
+    pub color: ColorOverTime,
+
+    /// The width of the segments, in world units.
+    pub width: f32,
+}
+
+impl Default for ConnectParticles {
+    fn default() -> Self {
+        Self {
+            color: ColorOverTime::default(),
+            width: 1.0,
+        }
+    }
+}
+
+/// Defines what space a particle should operate in.
+#[derive(Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Default)]
 pub enum ParticleSpace {
     /// Indicates particles should move relative to a parent.
     Local,
     /// Indicates particles should move relative to the world.
+    ///
+    /// Particles spawn at the emitter's world position and emit in the direction of its world rotation, like a
+    /// flamethrower's cone following the entity holding it. Once spawned, they move independently of the emitter.
+    #[default]
     World,
+    /// Like [`ParticleSpace::World`], but the particles remain tied to their emitter's lifetime.
+    ///
+    /// Particles spawn at the emitter's world position and rotation and then simulate in world space, but are
+    /// despawned along with the emitter, as if [`ParticleSystem::despawn_particles_with_system`] was set. This suits
+    /// effects that should leave a trail behind a moving entity, without outliving it.
+    WorldAnchored,
+}
+
+impl ParticleSpace {
+    /// Whether particles simulate in world space, which is the case for every space but [`ParticleSpace::Local`].
+    pub fn is_world(self) -> bool {
+        matches!(self, Self::World | Self::WorldAnchored)
+    }
 }
 
 /// Defines what texture to use for a particle
 #[derive(Debug, Clone, Reflect)]
+#[reflect(Default)]
 pub enum ParticleTexture {
     /// Indicates particles should use a given image texture
     Sprite(Handle<Image>),
@@ -63,6 +282,22 @@ pub enum ParticleTexture {
     },
 }
 
+impl ParticleTexture {
+    /// The image particles are drawn with, whether used directly or through an atlas.
+    pub fn image(&self) -> &Handle<Image> {
+        match self {
+            ParticleTexture::Sprite(image_handle) => image_handle,
+            ParticleTexture::TextureAtlas { texture, .. } => texture,
+        }
+    }
+}
+
+impl Default for ParticleTexture {
+    fn default() -> Self {
+        Self::Sprite(Handle::default())
+    }
+}
+
 impl From<Handle<Image>> for ParticleTexture {
     fn from(value: Handle<Image>) -> Self {
         ParticleTexture::Sprite(value)
@@ -71,6 +306,7 @@ impl From<Handle<Image>> for ParticleTexture {
 
 /// Defines how will be animated the texture atlas index
 #[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Default)]
 pub struct AnimatedIndex {
     /// At what indices are the different frames on a sprite sheet
     pub indices: Vec<usize>,
@@ -78,9 +314,47 @@ pub struct AnimatedIndex {
     pub time_step: f32,
     /// At what index (from the `indices` field) should start the animation
     pub step_offset: usize,
+    /// Whether each particle starts the animation at a random frame, on top of ``step_offset``
+    ///
+    /// This desyncs particles of the same system, which otherwise all play the animation in lockstep.
+    /// The frame is picked from the particle's seed, and the animation still loops through every frame.
+    pub random_frame_offset: bool,
+}
+
+impl Default for AnimatedIndex {
+    fn default() -> Self {
+        Self::from_fps(vec![0], 12.0)
+    }
 }
 
 impl AnimatedIndex {
+    /// Creates a new [`AnimatedIndex`] playing ``indices`` at ``fps`` frames per second.
+    ///
+    /// An ``fps`` of zero or less results in a single static frame.
+    pub fn from_fps(indices: Vec<usize>, fps: f32) -> Self {
+        Self {
+            indices,
+            time_step: if fps > 0.0 { 1.0 / fps } else { f32::INFINITY },
+            step_offset: 0,
+            random_frame_offset: false,
+        }
+    }
+
+    /// Makes each particle start the animation at a random frame, see ``random_frame_offset``.
+    pub fn with_random_frame_offset(mut self) -> Self {
+        self.random_frame_offset = true;
+        self
+    }
+
+    /// The animation played by a particle spawned with ``seed``, starting at its random frame if enabled.
+    pub(crate) fn for_particle(&self, seed: u32) -> Self {
+        let mut animated_index = self.clone();
+        if self.random_frame_offset && !self.indices.is_empty() {
+            animated_index.step_offset += seed as usize % self.indices.len();
+        }
+        animated_index
+    }
+
     /// Returns the first index of the animation
     pub fn get_at_start(&self) -> usize {
         let idx = if self.step_offset < self.indices.len() {
@@ -108,6 +382,46 @@ impl AnimatedIndex {
     }
 }
 
+/// Defines how the texture atlas index is animated by the distance a particle has moved
+///
+/// This is useful for particles that should look like they roll, such as wheels or rocks.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Default)]
+pub struct DistanceAnimatedIndex {
+    /// At what indices are the different frames on a sprite sheet
+    pub indices: Vec<usize>,
+    /// How far, in world units, the particle has to move to advance one frame
+    pub distance_step: f32,
+}
+
+impl Default for DistanceAnimatedIndex {
+    fn default() -> Self {
+        Self {
+            indices: vec![0],
+            distance_step: 1.0,
+        }
+    }
+}
+
+impl DistanceAnimatedIndex {
+    /// Returns the index corresponding to a given distance from the spawn point
+    ///
+    /// Returns `0` when there are no ``indices``.
+    pub fn get_at_distance(&self, distance: f32) -> usize {
+        if self.indices.is_empty() {
+            return 0;
+        }
+
+        // Disabling `cast_possible_truncation` so we can truncate the f32.
+        // Disabling `pedantic` because we use `abs()` before truncation. No sign loss possible.
+        #[allow(clippy::cast_possible_truncation, clippy::pedantic)]
+        // take only the integer part.
+        let steps_passed = (distance / self.distance_step).abs() as usize;
+
+        self.indices[steps_passed % self.indices.len()]
+    }
+}
+
 /// Defines the parameters of how a system and its particles behave.
 ///
 /// A [`ParticleSystem`] will emit particles until it reaches the ``system_duration_seconds`` or forever if ``looping`` is true, so long as the
@@ -117,43 +431,170 @@ impl AnimatedIndex {
 /// on the next frame.
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Component, Clone, Reflect)]
-#[reflect(Component)]
+#[reflect(Component, Default)]
 pub struct ParticleSystem {
     /// The maximum number of particles the system can have alive at any given time.
     pub max_particles: usize,
 
+    /// The minimum number of particles the system keeps alive while it is emitting.
+    ///
+    /// Whenever fewer particles are alive, the system spawns the missing ones at once, on top of its spawn rate.
+    /// This keeps ambient effects such as dust motes populated without tuning the rate against the lifetime.
+    /// This is capped to ``max_particles``. Defaults to `0`.
+    pub min_particles: usize,
+
     /// The texture used for each particle.
+    ///
+    /// Every particle of a system shares this same image handle, so they can be drawn together in as few
+    /// sprite batches as possible. Batches are broken when sprites with different images are interleaved
+    /// in draw order, so systems with different textures overlapping at the same depth may cost extra draw calls.
     pub texture: ParticleTexture,
 
     /// If provided, re-scale the texture size
     ///
-    /// This is simply passed directly to `Sprite::custom_size` or `TextureAtlasSprite::custom_size`
+    /// This is simply passed directly to `Sprite::custom_size` or `TextureAtlasSprite::custom_size`.
+    /// It is the same for every particle of the system, and does not prevent batching.
     pub rescale_texture: Option<Vec2>,
 
     /// The number of particles to spawn per second.
     ///
     /// This uses a [`ValueOverTime`] so that the spawn rate can vary over the lifetime of the system.
+    /// No particles are spawned from the rate while it is `0.0` or below, so a system with a zero rate and no
+    /// bursts spawns nothing even while [`Playing`], see [`ParticleSystem::emits_anything`].
     pub spawn_rate_per_second: ValueOverTime,
 
+    /// How the number of particles to emit is decided.
+    ///
+    /// Defaults to [`EmissionControl::Rate`], which uses ``spawn_rate_per_second``.
+    pub emission_control: EmissionControl,
+
+    /// An optional window of ``running_time``, in seconds, outside of which no particles are emitted.
+    ///
+    /// The window repeats every loop, so a `0.0..2.0` window in a looping 10 second system puffs for the first
+    /// 2 seconds of every cycle. A window covering the whole ``system_duration_seconds`` emits the whole time.
+    /// Only the continuous emission is affected, ``bursts`` still fire at their configured times.
+    pub emission_window: Option<Range<f32>>,
+
     /// The shape of the emitter.
     pub emitter_shape: EmitterShape,
 
+    /// Makes the whole emitter drift around its origin, e.g. for the wobbling base of a flame.
+    ///
+    /// Every frame, the noise is sampled at the origin and the current time, and the resulting offset is added to
+    /// the spawn positions sampled from ``emitter_shape``. The offset is in the emitter's frame, so it rotates and
+    /// scales with the emitter, and moves along with it in [`ParticleSpace::Local`]. Since [`Noise2D`] offsets can
+    /// reach twice its ``amplitude``, keep the amplitude to a few world units for a subtle wander.
+    pub emitter_wander: Option<Noise2D>,
+
+    /// Rotates the whole emission pattern around the emitter's `z` axis over the duration of the system, in radians.
+    ///
+    /// The angle is sampled at the current percentage of ``system_duration_seconds`` and rotates both the spawn
+    /// positions and directions sampled from ``emitter_shape``, on top of its own angles such as
+    /// [`CircleSegment::direction_angle`](crate::CircleSegment::direction_angle). A lerp from `0.0` to
+    /// [`std::f32::consts::TAU`] in a looping system makes a sprinkler turning once per loop.
+    pub emitter_spin: Option<ValueOverTime>,
+
+    /// Snaps the spawn position of each particle to a grid with cells of this size, in world units.
+    ///
+    /// This avoids shimmering in pixel-art games caused by particles spawning at fractional positions.
+    /// Only the x and y coordinates are snapped, in world space, even when using [`ParticleSpace::Local`].
+    pub spawn_grid_snap: Option<f32>,
+
+    /// Spreads each frame's particles along the path the emitter moved since the previous frame.
+    ///
+    /// Without this, a fast moving emitter spawns a cluster of particles at each frame's position, leaving visible
+    /// gaps between them. The last particle of the frame spawns at the current position, and the others are evenly
+    /// spaced back towards the previous one. The first frame has no previous position, so all particles spawn at the
+    /// current position. Not used with [`ParticleSpace::Local`], since local particles move along with the emitter.
+    pub interpolate_spawn_positions: bool,
+
     /// The initial movement speed of a particle.
     ///
     /// This value can be constant, or have added jitter to have particles move at varying speeds.
     pub initial_speed: JitteredValue,
 
+    /// An optional curve scaling ``initial_speed`` by how far from the emitter center a particle spawns.
+    ///
+    /// The curve is sampled with [`EmitterShape::normalized_distance`], from `0.0` at the center to `1.0` at the
+    /// edge of the emitter, e.g. to make a shockwave ring's outer particles faster than its inner ones.
+    /// It has no effect with [`EmitterShape::MeshSurface`].
+    pub speed_by_radius: Option<Curve<f32>>,
+
+    /// An optional cone half-angle, in radians, to randomly spread the emission direction by.
+    ///
+    /// The direction sampled from the [`EmitterShape`] is used as the axis of the cone, and each particle
+    /// picks a uniformly distributed direction within it around a random azimuth. `0.0` keeps the exact emitter
+    /// direction, while [`std::f32::consts::PI`] emits in a full sphere.
+    ///
+    /// Like every initial velocity, the spread direction is then projected onto the x/y plane.
+    pub direction_spread: Option<f32>,
+
+    /// An optional velocity that replaces the direction sampled from the [`EmitterShape`].
+    ///
+    /// When set, particles still spawn at positions from the ``emitter_shape``, but all move along this vector,
+    /// scaled by ``initial_speed``. The vector is sampled at the current percentage of ``system_duration_seconds``
+    /// when each particle spawns, so the emission direction can change over the lifetime of the system.
+    pub initial_velocity_override: Option<VectorOverTime>,
+
+    /// An optional set of velocities to pick each particle's initial velocity from.
+    ///
+    /// When set, this takes precedence over the emitter direction, ``direction_spread``,
+    /// ``initial_velocity_override``, ``initial_speed`` and ``speed_by_radius``: the sampled vector is used as the
+    /// particle's velocity as-is. Particles still spawn at positions from the ``emitter_shape``, and the z component
+    /// of the velocity is ignored like any other particle velocity.
+    pub initial_velocity: Option<RandomValue<Vec3>>,
+
     /// Modifiers affecting the particle velocity.
     ///
     /// They can be stacked, and will be applied in order.
     /// No modifiers makes the particle move at its ``initial_speed`` for its lifetime.
+    ///
+    /// Live particles always use the system's current modifiers, so changes made at runtime also apply to
+    /// particles that have already been spawned.
     pub velocity_modifiers: Vec<VelocityModifier>,
 
+    /// Locks axes of the particle velocity, applied every frame after ``velocity_modifiers``.
+    ///
+    /// Like ``velocity_modifiers``, live particles always use the system's current constraint.
+    pub velocity_constraint: Option<VelocityConstraint>,
+
+    /// The mass of each particle, dividing the effect of the force-like ``velocity_modifiers`` on it.
+    ///
+    /// Only [`VelocityModifier::Vector`] and [`VelocityModifier::Drag`] are divided by the mass, so heavier particles
+    /// are pushed less and keep their speed longer against drag. [`VelocityModifier::GlobalGravity`],
+    /// [`VelocityModifier::Scalar`], [`VelocityModifier::Noise`], and [`VelocityModifier::Orbit`] are accelerations
+    /// that affect every mass alike. Jitter makes particles from one system respond differently, e.g. heavy rocks and light dust from a single
+    /// explosion. Masses are clamped to at least `0.001`. Defaults to `1.0`, leaving the modifiers unchanged.
+    pub mass: JitteredValue,
+
     /// The lifetime of each particle, in seconds.
     ///
     /// This value can have jitter, causing lifetimes to vary per particle.
+    /// Sampled lifetimes are clamped to at least one millisecond, with a warning logged once per system.
     pub lifetime: JitteredValue,
 
+    /// An optional curve scaling the sampled ``lifetime`` of each particle by its initial speed.
+    ///
+    /// The curve is sampled with the speed sampled from ``initial_speed``, divided by the largest speed its jitter
+    /// range allows, so `1.0` is the fastest particle. This is sampled before ``speed_by_radius`` is applied, and
+    /// even when ``initial_velocity`` replaces the speed. For example, a curve rising from `0.5` to `1.0` makes the
+    /// slowest sparks fade out quicker than the fastest ones.
+    pub lifetime_by_speed: Option<Curve<f32>>,
+
+    /// The age, in seconds, particles start at when spawned.
+    ///
+    /// Jitter on this value starts particles partway through their lifetime, varying their color and scale
+    /// right away. The sampled age is clamped to be less than the particle's lifetime, so particles are never
+    /// spawned dead.
+    pub initial_age: JitteredValue,
+
+    /// Fractions of their lifetime, from `0.0` to `1.0`, at which particles send a
+    /// [`ParticleLifetimeThreshold`](crate::events::ParticleLifetimeThreshold) event.
+    ///
+    /// Each particle sends one event per threshold, the frame its age crosses it. Thresholds already behind a
+    /// particle's ``initial_age`` when it spawns are skipped.
+    pub lifetime_events: Vec<f32>,
+
     /// The color of each particle over time.
     ///
     /// Color is used to modify the ``default_sprite``. A constant value of [`bevy_color::prelude::Color::WHITE`] will make the sprite appear with no modifications.
@@ -161,11 +602,45 @@ pub struct ParticleSystem {
     /// This can vary over time and be used to modify alpha as well.
     pub color: ColorOverTime,
 
+    /// Extra colors composited on top of ``color``, in order.
+    ///
+    /// Each layer is evaluated along the particle's lifetime like ``color``, and blended onto the colors below it
+    /// with its [`ColorBlendOp`], e.g. to add a highlight pulse onto a base color ramp.
+    pub color_layers: Vec<(ColorBlendOp, ColorOverTime)>,
+
+    /// What ``color`` and ``color_layers`` are sampled along, instead of the particle's lifetime.
+    ///
+    /// For example, [`ColorDriver::Speed`] makes particles glow hotter the faster they move. ``alpha_over_time``
+    /// always follows the lifetime.
+    pub color_driver: ColorDriver,
+
+    /// Replaces the alpha of ``color``, after ``color_layers`` are composited, over the lifetime of each particle.
+    ///
+    /// Combined with a constant ``color``, this fades particles without changing their hue.
+    pub alpha_over_time: Option<AlphaOverTime>,
+
+    /// Randomly flickers the alpha of each particle independently, useful for fire and electricity.
+    ///
+    /// An intensity of `0.0` disables the flicker.
+    pub flicker: Option<FlickerModifier>,
+
+    /// Draws an outline behind each particle.
+    ///
+    /// See [`ParticleOutline`] for the cost of enabling this.
+    pub outline: Option<ParticleOutline>,
+
+    /// Connects each particle to the one spawned right before it with a line segment.
+    ///
+    /// See [`ConnectParticles`] for how the segments are drawn.
+    pub connect_particles: Option<ConnectParticles>,
+
     /// The initial scale of a particle.
     ///
     /// This value can be constant, or have added jitter to have particles with different sizes
     ///
-    /// This value is multiplied with scale to produce the final scale.
+    /// This value is multiplied with scale to produce the final scale, throughout the particle's lifetime, so
+    /// the shape of the ``scale`` curve is the same for every particle regardless of its initial size.
+    /// Negative values keep their sign, flipping the sprite on both axes.
     pub initial_scale: JitteredValue,
 
     /// The scale or size of the particle over time.
@@ -175,8 +650,22 @@ pub struct ParticleSystem {
     /// Multiplied with [`initial_scale`][`Self::initial_scale`] to produce the final scale.
     pub scale: ValueOverTime,
 
+    /// Multiplies the particle's scale on each axis, to stretch or mirror the sprite.
+    ///
+    /// A negative component mirrors the sprite along that axis, e.g. `Vec3::new(-1.0, 1.0, 1.0)` flips it
+    /// horizontally. A zero `x` or `y` component makes particles invisible, and logs a warning.
+    pub axis_scale: Vec3,
+
     /// The rotation of a particle around the `z` access at spawn in radian.
-    pub initial_rotation: JitteredValue,
+    ///
+    /// This can have jitter, see [`InitialRotation::symmetric`], or be picked from a set of angles.
+    pub initial_rotation: InitialRotation,
+
+    /// Derives the base rotation at spawn from the particle's position around the emitter's center.
+    ///
+    /// Defaults to [`InitialRotationMode::Fixed`], using only the ``initial_rotation``. Other modes take precedence
+    /// over ``rotate_to_movement_direction``, while ``align_to_velocity`` replaces the rotation from the first frame.
+    pub initial_rotation_mode: InitialRotationMode,
 
     /// The speed at which the particle rotates in radian per second.
     pub rotation_speed: JitteredValue,
@@ -190,37 +679,135 @@ pub struct ParticleSystem {
     /// to account for needing to apply a base rotation to the sprite.
     pub rotate_to_movement_direction: bool,
 
+    /// Keeps the particle facing its current velocity every frame, not only at spawn.
+    ///
+    /// The ``initial_rotation`` and ``rotation_speed`` are applied as a spin on top of the velocity-aligned
+    /// rotation, so a particle can both follow its movement direction and spin visually. While a particle
+    /// is not moving, its last alignment is kept.
+    pub align_to_velocity: bool,
+
     /// Whether or not the system will start over automatically.
     pub looping: bool,
 
+    /// How many times a ``looping`` system runs through its duration before finishing.
+    ///
+    /// Defaults to [`LoopCount::Infinite`], looping forever. This is ignored when ``looping`` is `false`.
+    pub loop_count: LoopCount,
+
     /// How long the system will emit particles for.
     pub system_duration_seconds: f32,
 
     /// A maximum distance a particle can travel before being despawned.
     pub max_distance: Option<f32>,
 
+    /// Despawns particles once they are outside of the view of all active cameras.
+    ///
+    /// This is a more precise alternative to ``max_distance`` for long-lived particles drifting off screen.
+    /// Particles are kept alive as long as any active camera can see them, and while no camera is active at all.
+    pub despawn_when_offscreen: Option<OffscreenDespawn>,
+
+    /// Despawns particles once they leave this region.
+    ///
+    /// Unlike ``max_distance``, which is measured from where each particle spawned, this confines every particle to
+    /// the same volume, such as smoke dissipating as it leaves a room.
+    pub despawn_region: Option<ParticleRegion>,
+
+    /// Whether particles spawned outside of ``despawn_region`` are despawned right away.
+    ///
+    /// When `false` (the default), particles spawned outside of the region ignore it for their whole lifetime.
+    pub despawn_if_spawned_outside: bool,
+
     /// Set a fixed/constant z value (useful for 2D to set a fixed z-depth).
     pub z_value_override: Option<JitteredValue>,
 
+    /// Places particles at the emitter's z value plus this offset, keeping their draw order relative to the emitter.
+    ///
+    /// For example, an offset of `-0.1` keeps smoke trailing a character rendered just behind it as the character
+    /// moves between layers. In ``ParticleSpace::Local`` the offset is applied in the emitter's local space.
+    ///
+    /// ``z_value_override`` takes precedence over this value when both are set.
+    pub z_offset_from_emitter: Option<f32>,
+
+    /// An optional limit on the total number of particles the system will ever emit.
+    ///
+    /// Once this many particles have been spawned, including bursts, the system stops emitting regardless of
+    /// its spawn rate or duration, and is considered finished once its remaining particles have died, even if
+    /// ``looping`` is `true`.
+    pub total_particles_to_emit: Option<usize>,
+
+    /// An optional limit on how many particles the system spawns in a single frame.
+    ///
+    /// Particles over the limit, such as from a large burst, are held back and spawned on the following frames
+    /// instead of being dropped. This bounds the cost of a single frame, at the expense of delaying part of the
+    /// emission. The system keeps spawning held back particles after the end of its duration, and only finishes
+    /// once they have all spawned.
+    pub max_spawns_per_frame: Option<usize>,
+
     /// A series of bursts of particles at configured times.
+    ///
+    /// Bursts fire in order of their resolved time, so absolute and relative bursts can be mixed in any order.
+    /// Every burst that is due fires on the same frame, including several bursts sharing the same time.
+    /// Particles of a burst that would exceed ``max_particles`` are dropped, unless ``deferred_bursts`` is set.
     pub bursts: Vec<ParticleBurst>,
 
+    /// A burst fired at a fixed interval for as long as the system emits, in addition to ``bursts``.
+    pub repeating_burst: Option<RepeatingBurst>,
+
+    /// Whether burst particles that don't fit within ``max_particles`` are spawned later instead of dropped.
+    ///
+    /// The remainder is queued and spawned on the following frames as particles die and free up room. This applies
+    /// to ``bursts``, ``repeating_burst`` and particles requested by a [`BeatEmitter`]. Queued particles are dropped
+    /// once they have waited ``deferred_burst_timeout`` seconds, or when the system stops emitting.
+    pub deferred_bursts: bool,
+
+    /// How long, in seconds, deferred burst particles wait for room before being dropped.
+    ///
+    /// The wait restarts whenever more particles are deferred. Only used with ``deferred_bursts``.
+    pub deferred_burst_timeout: f32,
+
     /// What coordinate space particles should use.
     pub space: ParticleSpace,
 
+    /// An optional seed making the particles of this system reproducible.
+    ///
+    /// Every random choice made when spawning a particle, such as its position, direction, speed, lifetime, scale,
+    /// rotation and atlas index, is drawn in a fixed order from a single stream derived from this seed and the
+    /// number of particles spawned so far. Two systems with the same seed and settings, stepped with the same
+    /// frame times, spawn identical particles. When `None`, a thread-local random generator is used.
+    pub seed: Option<u64>,
+
     /// Dictates whether this system respects Bevy's time scaling by using [`bevy_time::Virtual`]  when true, or [`bevy_time::Real`] when false.
     pub use_scaled_time: bool,
 
+    /// The longest frame, in seconds, this system simulates at once.
+    ///
+    /// Longer frames, such as a hitch while loading, are treated as if only this much time passed, for spawning,
+    /// aging and moving particles alike. This keeps a hitch from spawning a large batch of particles or teleporting
+    /// them across the screen, at the cost of the system briefly falling behind. With ``use_scaled_time``, the
+    /// scaled frame time is clamped. When `None`, frames are never clamped.
+    pub max_delta_time: Option<f32>,
+
+    /// A multiplier on how fast the particles of this system move and spin, on top of ``use_scaled_time``.
+    ///
+    /// Spawning is not affected, so `0.5` makes particles drift in slow motion while the system emits at its usual
+    /// pace. At `0.0` particles stand still, while still aging and changing color.
+    pub simulation_speed: f32,
+
+    /// Whether ``simulation_speed`` also applies to how fast particles age, so their lifetime and color over time
+    /// slow down along with their movement.
+    pub simulation_speed_affects_lifetime: bool,
+
     /// Indicates that the entity the [`ParticleSystem`] is on should be despawned when the system completes and has no more particles.
     ///
     /// Defaults to `false`.
     ///
-    /// Note that this will never trigger on a system that has ``looping`` set to `true`.
+    /// Note that this will never trigger on a system that loops forever, see ``loop_count``.
     pub despawn_on_finish: bool,
 
     /// Indicates whether alive particles should be despawned when the system itself is despawned.
     ///
     /// When this is `false` (the default), particles will live out their lifetime even if the system has been despawned.
+    /// Particles in [`ParticleSpace::WorldAnchored`] are always despawned with their system.
     pub despawn_particles_with_system: bool,
 }
 
@@ -228,26 +815,65 @@ impl Default for ParticleSystem {
     fn default() -> Self {
         Self {
             max_particles: 100,
+            min_particles: 0,
             texture: ParticleTexture::Sprite(Handle::default()),
             rescale_texture: None,
             spawn_rate_per_second: 5.0.into(),
+            emission_control: EmissionControl::default(),
+            emission_window: None,
             emitter_shape: EmitterShape::default(),
+            emitter_wander: None,
+            emitter_spin: None,
+            spawn_grid_snap: None,
+            interpolate_spawn_positions: false,
             initial_speed: 1.0.into(),
+            speed_by_radius: None,
+            direction_spread: None,
+            initial_velocity_override: None,
+            initial_velocity: None,
             velocity_modifiers: vec![],
+            velocity_constraint: None,
+            mass: 1.0.into(),
             lifetime: 5.0.into(),
+            lifetime_by_speed: None,
+            initial_age: 0.0.into(),
+            lifetime_events: Vec::new(),
             color: ColorOverTime::default(),
+            color_layers: Vec::new(),
+            color_driver: ColorDriver::Lifetime,
+            alpha_over_time: None,
+            flicker: None,
+            outline: None,
+            connect_particles: None,
             initial_scale: 1.0.into(),
             scale: 1.0.into(),
+            axis_scale: Vec3::ONE,
             initial_rotation: 0.0.into(),
+            initial_rotation_mode: InitialRotationMode::Fixed,
             rotation_speed: 0.0.into(),
             rotate_to_movement_direction: false,
+            align_to_velocity: false,
             looping: true,
+            loop_count: LoopCount::Infinite,
             system_duration_seconds: 5.0,
             max_distance: None,
+            despawn_when_offscreen: None,
+            despawn_region: None,
+            despawn_if_spawned_outside: false,
             z_value_override: None,
+            z_offset_from_emitter: None,
+            total_particles_to_emit: None,
+            max_spawns_per_frame: None,
             bursts: Vec::default(),
+            repeating_burst: None,
+            deferred_bursts: false,
+            deferred_burst_timeout: 1.0,
             space: ParticleSpace::World,
+            seed: None,
             use_scaled_time: true,
+            max_delta_time: None,
+            simulation_speed: 1.0,
+            simulation_speed_affects_lifetime: false,
             despawn_on_finish: false,
             despawn_particles_with_system: false,
         }
@@ -265,36 +891,284 @@ impl ParticleSystem {
             ..Default::default()
         }
     }
-}
 
-/// An individual Particle, spawned by a [`ParticleSystem`]
-///
-/// The ``parent_entity`` should link to the entity with the spawning [`ParticleSystem`] on it.
-///
-/// If the ``parent_entity`` no longer exists or does not contain a [`ParticleSystem`] the particle will
-/// be despawned immediately.
-///
-/// The parent should be linked here explicitly because particles may operate in world space, and not be actual
-/// children of the [`ParticleSystem`] itself.
-#[derive(Debug, Component)]
-pub struct Particle {
-    /// The entity on which the spawning [`ParticleSystem`] resides.
-    pub parent_system: Entity,
-
-    /// The total lifetime of the particle.
+    /// A oneshot system spawning a single, still particle that grows from nothing to ``radius`` while fading out
+    /// over ``duration`` seconds, like the shockwave of an explosion.
     ///
-    /// When the [`Lifetime`] component value reaches this value, the particle is considered dead and will be despawned.
-    pub max_lifetime: f32,
-
-    /// The maximum distance traveled for the particle.
+    /// ``texture`` should be a ring. It is drawn two units wide at a scale of `1.0`, so the scale of the particle
+    /// matches its radius in world units.
     ///
-    /// When the [`DistanceTraveled`] component value reaches this value, the particle is considered dead and will be despawned.
-    pub max_distance: Option<f32>,
+    /// ## Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_particle_systems::*;
+    /// fn explode(mut commands: Commands, asset_server: Res<AssetServer>) {
+    ///     commands
+    ///         .spawn(ParticleSystemBundle {
+    ///             particle_system: ParticleSystem::shockwave(asset_server.load("ring.png"), 120.0, 0.4),
+    ///             ..ParticleSystemBundle::default()
+    ///         })
+    ///         .insert(Playing);
+    /// }
+    /// ```
+    pub fn shockwave(texture: impl Into<ParticleTexture>, radius: f32, duration: f32) -> Self {
+        Self {
+            max_particles: 1,
+            texture: texture.into(),
+            rescale_texture: Some(Vec2::splat(2.0)),
+            spawn_rate_per_second: 0.0.into(),
+            bursts: vec![ParticleBurst::new(0.0, 1)],
+            emitter_shape: EmitterShape::circle(0.0),
+            initial_speed: 0.0.into(),
+            lifetime: duration.into(),
+            system_duration_seconds: duration,
+            scale: (0.0..radius).into(),
+            alpha_over_time: Some(AlphaOverTime((1.0..0.0).into())),
+            ..Self::oneshot()
+        }
+    }
 
-    /// Whether the particle will respect scaled time in its transformations.
+    /// How far the system is through its ``system_duration_seconds``, from `0.0` to `1.0`.
     ///
-    /// This is copied from [`ParticleSystem::use_scaled_time`] on spawn.
-    pub use_scaled_time: bool,
+    /// Looping systems go back to `0.0` at the start of every loop.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use bevy_particle_systems::{ParticleSystem, RunningState};
+    /// let particle_system = ParticleSystem {
+    ///     system_duration_seconds: 4.0,
+    ///     ..ParticleSystem::default()
+    /// };
+    /// let mut running_state = RunningState::default();
+    /// running_state.running_time = 1.0;
+    /// assert_eq!(particle_system.progress(&running_state), 0.25);
+    /// ```
+    pub fn progress(&self, running_state: &RunningState) -> f32 {
+        if self.system_duration_seconds <= 0.0 {
+            return 1.0;
+        }
+        (running_state.running_time / self.system_duration_seconds).clamp(0.0, 1.0)
+    }
+
+    /// Whether the system is configured to emit any particles at all.
+    ///
+    /// A system emits nothing when its ``spawn_rate_per_second`` is never above `0.0` and it has no bursts or
+    /// ``min_particles``, when ``max_particles`` or ``total_particles_to_emit`` is `0`, or when
+    /// [`EmissionControl::SteadyState`] targets no live particles. Such a system can still be [`Playing`], e.g. as a
+    /// template that is filled in later, and runs its duration without spawning. Particles requested by a
+    /// [`BeatEmitter`] are not taken into account.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use bevy_particle_systems::ParticleSystem;
+    /// let template = ParticleSystem {
+    ///     spawn_rate_per_second: 0.0.into(),
+    ///     ..ParticleSystem::default()
+    /// };
+    /// assert!(!template.emits_anything());
+    /// assert!(ParticleSystem::default().emits_anything());
+    /// ```
+    pub fn emits_anything(&self) -> bool {
+        let from_rate = match self.emission_control {
+            EmissionControl::Rate => self.spawn_rate_per_second.can_be_positive(),
+            EmissionControl::SteadyState { target_alive } => target_alive > 0,
+        };
+        let from_bursts = self.bursts.iter().any(|burst| burst.count > 0)
+            || self
+                .repeating_burst
+                .as_ref()
+                .is_some_and(|burst| burst.count + burst.jitter > 0);
+
+        (from_rate || from_bursts || self.min_particles > 0)
+            && self.max_particles > 0
+            && self.total_particles_to_emit != Some(0)
+    }
+
+    /// Whether the system has stopped emitting for good, either because it reached the end of its last loop or
+    /// emitted its ``total_particles_to_emit``, and has no particles held back by ``max_spawns_per_frame``.
+    ///
+    /// Particles it already emitted may still be alive, see [`ParticleSystem::is_finished`].
+    pub fn is_done_emitting(&self, running_state: &RunningState) -> bool {
+        self.emission_ended(running_state) && running_state.pending_spawns == 0
+    }
+
+    /// Whether the system no longer emits new particles, though it may still spawn held back ones.
+    pub(crate) fn emission_ended(&self, running_state: &RunningState) -> bool {
+        self.total_particles_to_emit
+            .is_some_and(|total| running_state.total_spawned >= total)
+            || (!self.loops_again(running_state)
+                && running_state.running_time >= self.system_duration_seconds)
+    }
+
+    /// Whether the system starts over once it reaches the end of its current loop.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use bevy_particle_systems::{LoopCount, ParticleSystem, RunningState};
+    /// let thrice = ParticleSystem {
+    ///     loop_count: LoopCount::Finite(3),
+    ///     ..ParticleSystem::default()
+    /// };
+    /// let mut running_state = RunningState::default();
+    /// assert!(thrice.loops_again(&running_state));
+    /// running_state.completed_loops = 2;
+    /// assert!(!thrice.loops_again(&running_state));
+    ///
+    /// let once = ParticleSystem {
+    ///     loop_count: LoopCount::Finite(0),
+    ///     ..ParticleSystem::default()
+    /// };
+    /// assert!(!once.loops_again(&RunningState::default()));
+    /// ```
+    pub fn loops_again(&self, running_state: &RunningState) -> bool {
+        self.looping
+            && match self.loop_count {
+                LoopCount::Infinite => true,
+                LoopCount::Finite(count) => running_state.completed_loops + 1 < count,
+            }
+    }
+
+    /// Moves the playhead of the system to ``time`` seconds into its current loop, e.g. to sync it with a cutscene.
+    ///
+    /// ``time`` is clamped between `0.0` and ``system_duration_seconds``. Bursts and repeating bursts scheduled
+    /// before ``time`` are marked as fired, so they aren't all fired at once, while those from ``time`` onwards fire
+    /// as the system plays. Particles waiting to spawn are dropped. Existing particles are left untouched and no
+    /// particles are spawned for the skipped time, see [`SeekTo`] to also clear particles when seeking backwards.
+    ///
+    /// Seeking backwards also restarts the counts compared against ``total_particles_to_emit`` and ``loop_count``,
+    /// so a finished system emits again from ``time``.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use bevy_particle_systems::{BurstIndex, ParticleBurst, ParticleSystem, RunningState};
+    /// let particle_system = ParticleSystem {
+    ///     bursts: vec![ParticleBurst::new(1.0, 10), ParticleBurst::new(3.0, 10)],
+    ///     ..ParticleSystem::default()
+    /// };
+    /// let mut running_state = RunningState::default();
+    /// let mut burst_index = BurstIndex::default();
+    /// particle_system.seek(&mut running_state, &mut burst_index, 2.0);
+    /// assert_eq!(running_state.running_time, 2.0);
+    /// assert_eq!(burst_index.0, 1);
+    ///
+    /// running_state.completed_loops = 1;
+    /// particle_system.seek(&mut running_state, &mut burst_index, 0.0);
+    /// assert_eq!(running_state.completed_loops, 0);
+    /// ```
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn seek(&self, running_state: &mut RunningState, burst_index: &mut BurstIndex, time: f32) {
+        let time = time.clamp(0.0, self.system_duration_seconds.max(0.0));
+        if time < running_state.running_time {
+            running_state.total_spawned = 0;
+            running_state.completed_loops = 0;
+            running_state.seeded_draws = 0;
+        }
+        running_state.running_time = time;
+        running_state.deferred_burst_particles = 0;
+        running_state.deferred_burst_wait = 0.0;
+        running_state.pending_spawns = 0;
+        running_state.requested_particles = 0;
+        burst_index.0 = self
+            .bursts
+            .iter()
+            .filter(|burst| burst.resolved_time(self.system_duration_seconds) < time)
+            .count();
+        running_state.repeating_bursts_fired = match self.repeating_burst {
+            Some(repeating_burst) if repeating_burst.interval > 0.0 => {
+                (time / repeating_burst.interval).ceil() as usize
+            }
+            _ => 0,
+        };
+    }
+
+    /// Whether the system is done emitting and all of its particles have died.
+    ///
+    /// This is the condition on which the system stops [`Playing`], or is despawned if ``despawn_on_finish`` is set.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_particle_systems::{ParticleCount, ParticleSystem, RunningState};
+    /// fn report_finished_effects(
+    ///     systems: Query<(Entity, &ParticleSystem, &RunningState, &ParticleCount)>,
+    /// ) {
+    ///     for (entity, particle_system, running_state, particle_count) in &systems {
+    ///         if particle_system.is_finished(running_state, particle_count) {
+    ///             info!("{entity} has finished");
+    ///         }
+    ///     }
+    /// }
+    /// # bevy::ecs::system::assert_is_system(report_finished_effects);
+    /// ```
+    pub fn is_finished(
+        &self,
+        running_state: &RunningState,
+        particle_count: &ParticleCount,
+    ) -> bool {
+        self.is_done_emitting(running_state) && particle_count.0 == 0
+    }
+}
+
+/// An individual Particle, spawned by a [`ParticleSystem`]
+///
+/// The ``parent_entity`` should link to the entity with the spawning [`ParticleSystem`] on it.
+///
+/// If the ``parent_entity`` no longer exists or does not contain a [`ParticleSystem`] the particle will
+/// be despawned immediately.
+///
+/// The parent should be linked here explicitly because particles may operate in world space, and not be actual
+/// children of the [`ParticleSystem`] itself.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Component)]
+pub struct Particle {
+    /// The entity on which the spawning [`ParticleSystem`] resides.
+    pub parent_system: Entity,
+
+    /// The total lifetime of the particle.
+    ///
+    /// When the [`Lifetime`] component value reaches this value, the particle is considered dead and will be despawned.
+    pub max_lifetime: f32,
+
+    /// The maximum distance traveled for the particle.
+    ///
+    /// When the [`DistanceTraveled`] component value reaches this value, the particle is considered dead and will be despawned.
+    pub max_distance: Option<f32>,
+
+    /// Whether and how the particle is despawned once outside of the view of all cameras.
+    ///
+    /// This is copied from [`ParticleSystem::despawn_when_offscreen`] on spawn.
+    pub despawn_when_offscreen: Option<OffscreenDespawn>,
+
+    /// The region the particle is despawned when leaving.
+    ///
+    /// This is copied from [`ParticleSystem::despawn_region`] on spawn, unless the particle spawned outside of it.
+    pub despawn_region: Option<ParticleRegion>,
+
+    /// Whether the particle will respect scaled time in its transformations.
+    ///
+    /// This is copied from [`ParticleSystem::use_scaled_time`] on spawn.
+    pub use_scaled_time: bool,
+
+    /// The longest frame, in seconds, the particle simulates at once.
+    ///
+    /// This is copied from [`ParticleSystem::max_delta_time`] on spawn.
+    pub max_delta_time: Option<f32>,
+
+    /// The multiplier on the particle's movement and spin.
+    ///
+    /// This is copied from [`ParticleSystem::simulation_speed`] on spawn.
+    pub simulation_speed: f32,
+
+    /// Whether ``simulation_speed`` also applies to the particle's aging.
+    ///
+    /// This is copied from [`ParticleSystem::simulation_speed_affects_lifetime`] on spawn.
+    pub simulation_speed_affects_lifetime: bool,
 
     /// The initial scale of the particle, multiplied with `scale` to produce
     /// the final scale of the particle.
@@ -305,16 +1179,71 @@ pub struct Particle {
     /// This is copied from [`ParticleSystem::scale`] on spawn.
     pub scale: ValueOverTime,
 
+    /// The multiplier on each axis of the particle's scale.
+    ///
+    /// This is copied from [`ParticleSystem::axis_scale`] on spawn.
+    pub axis_scale: Vec3,
+
     /// Velocity Modifiers of this particle.
     ///
-    /// This is copied from [`ParticleSystem::velocity_modifiers`] on spawn.
+    /// This is copied from [`ParticleSystem::velocity_modifiers`] on spawn, and only used once the spawning
+    /// system has been despawned. Until then the system's current modifiers are used.
     pub velocity_modifiers: Vec<VelocityModifier>,
 
+    /// Axes of the velocity locked after applying the modifiers.
+    ///
+    /// This is copied from [`ParticleSystem::velocity_constraint`] on spawn, and only used once the spawning
+    /// system has been despawned. Until then the system's current constraint is used.
+    pub velocity_constraint: Option<VelocityConstraint>,
+
+    /// The mass dividing the effect of the force-like velocity modifiers on the particle.
+    ///
+    /// This is sampled from [`ParticleSystem::mass`] on spawn.
+    pub mass: f32,
+
     /// The speed, in radian per second, at which the particle rotates.
     ///
     /// This is chosen from [`ParticleSystem::rotation_speed`] on spawn.
     pub rotation_speed: f32,
 
+    /// The rotation, in radian, the particle was spawned with.
+    ///
+    /// This is chosen from [`ParticleSystem::initial_rotation`] on spawn.
+    pub initial_rotation: f32,
+
+    /// Whether the particle is rotated to face its velocity every frame.
+    ///
+    /// This is copied from [`ParticleSystem::align_to_velocity`] on spawn.
+    pub align_to_velocity: bool,
+
+    /// The color the particle's color is multiplied by.
+    ///
+    /// This is copied from the [`ParentTint`] of the spawning system, or white if it has none.
+    pub tint: Color,
+
+    /// What the particle's color is sampled along.
+    ///
+    /// This is copied from [`ParticleSystem::color_driver`] on spawn.
+    pub color_driver: ColorDriver,
+
+    /// The alpha of the particle over its lifetime, replacing the alpha of its color.
+    ///
+    /// This is copied from [`ParticleSystem::alpha_over_time`] on spawn.
+    pub alpha_over_time: Option<AlphaOverTime>,
+
+    /// Flicker applied to the particle's alpha.
+    ///
+    /// This is copied from [`ParticleSystem::flicker`] on spawn.
+    pub flicker: Option<FlickerModifier>,
+
+    /// A random value chosen on spawn, used to vary per-particle effects such as flicker.
+    pub seed: u32,
+
+    /// How far through its duration the parent system was when the particle spawned, from `0.0` to `1.0`.
+    ///
+    /// This drives the palette of [`ColorOverTime::AnimatedLerp`].
+    pub system_pct: f32,
+
     /// Indicates whether the particle should be cleaned up when the parent system is despawned
     pub despawn_with_parent: bool,
 }
@@ -325,11 +1254,27 @@ impl Default for Particle {
             parent_system: Entity::from_raw(0),
             max_lifetime: f32::default(),
             max_distance: None,
+            despawn_when_offscreen: None,
+            despawn_region: None,
             use_scaled_time: true,
+            max_delta_time: None,
+            simulation_speed: 1.0,
+            simulation_speed_affects_lifetime: false,
             initial_scale: 1.0,
             scale: 1.0.into(),
+            axis_scale: Vec3::ONE,
             rotation_speed: 0.0,
+            initial_rotation: 0.0,
+            align_to_velocity: false,
+            tint: Color::WHITE,
+            color_driver: ColorDriver::Lifetime,
+            alpha_over_time: None,
+            flicker: None,
+            seed: 0,
+            system_pct: 0.0,
             velocity_modifiers: vec![],
+            velocity_constraint: None,
+            mass: 1.0,
             despawn_with_parent: false,
         }
     }
@@ -340,14 +1285,28 @@ impl Default for Particle {
 /// This is separated into its own component because the [`ColorOverTime`]
 /// is used mutably in the case of Gradients to improve performance.
 ///
-/// Its initial value on particle spawn is copied from [`ParticleSystem::color`]
+/// Its initial value on particle spawn is copied from [`ParticleSystem::color`] and
+/// [`ParticleSystem::color_layers`].
 #[derive(Debug, Component, Default)]
-pub struct ParticleColor(pub ColorOverTime);
+pub struct ParticleColor(pub ColorOverTime, pub Vec<(ColorBlendOp, ColorOverTime)>);
 
 /// Contains how long a particle has been alive, in seconds.
 #[derive(Debug, Component, Default)]
 pub struct Lifetime(pub f32);
 
+/// Tracks which of the [`ParticleSystem::lifetime_events`] a particle has crossed.
+///
+/// This is only added to particles of systems with ``lifetime_events``.
+#[derive(Debug, Component, Clone, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct LifetimeThresholds {
+    /// The fractions of its lifetime the particle sends events at, copied from the system on spawn.
+    pub thresholds: Vec<f32>,
+
+    /// The fraction of its lifetime the particle had lived when thresholds were last checked.
+    pub last_lifetime_pct: f32,
+}
+
 /// Contains how far, in world units, a particle has moved since spawning.
 #[derive(Debug, Component, Default)]
 pub struct DistanceTraveled {
@@ -371,43 +1330,749 @@ impl Velocity {
     /// ``ignore_z`` should generally be set to true for 2d use cases, so trajectories ignore the z dimension and a particle stays at a consistent depth.
     pub fn new(velocity: Vec3, ignore_z: bool) -> Self {
         if ignore_z {
-            Self(Vec3::new(velocity.x, velocity.y, 0.0).normalize() * velocity.length())
+            Self(Vec3::new(velocity.x, velocity.y, 0.0).normalize_or_zero() * velocity.length())
         } else {
             Self(velocity)
         }
     }
+
+    /// Adds ``impulse`` to the velocity, e.g. to push particles away from an explosion.
+    ///
+    /// ```
+    /// # use bevy::math::Vec3;
+    /// # use bevy_particle_systems::Velocity;
+    /// let mut velocity = Velocity(Vec3::new(2.0, 0.0, 0.0));
+    /// velocity.add_impulse(Vec3::new(0.0, 3.0, 0.0));
+    /// assert_eq!(velocity.0, Vec3::new(2.0, 3.0, 0.0));
+    /// ```
+    pub fn add_impulse(&mut self, impulse: Vec3) {
+        self.0 += impulse;
+    }
+
+    /// The speed of the particle, in units per second.
+    ///
+    /// ```
+    /// # use bevy::math::Vec3;
+    /// # use bevy_particle_systems::Velocity;
+    /// assert_eq!(Velocity(Vec3::new(3.0, 4.0, 0.0)).speed(), 5.0);
+    /// ```
+    pub fn speed(&self) -> f32 {
+        self.0.length()
+    }
+
+    /// The normalized direction the particle moves in, or [`Vec3::ZERO`] if it is not moving.
+    ///
+    /// ```
+    /// # use bevy::math::Vec3;
+    /// # use bevy_particle_systems::Velocity;
+    /// assert_eq!(Velocity(Vec3::new(0.0, -5.0, 0.0)).direction(), Vec3::NEG_Y);
+    /// assert_eq!(Velocity(Vec3::ZERO).direction(), Vec3::ZERO);
+    /// ```
+    pub fn direction(&self) -> Vec3 {
+        self.0.normalize_or_zero()
+    }
+
+    /// Changes the speed of the particle, keeping its direction.
+    ///
+    /// A particle that is not moving has no direction, and stays still.
+    ///
+    /// ```
+    /// # use bevy::math::Vec3;
+    /// # use bevy_particle_systems::Velocity;
+    /// let mut velocity = Velocity(Vec3::new(3.0, 4.0, 0.0));
+    /// velocity.set_speed(10.0);
+    /// assert_eq!(velocity.0, Vec3::new(6.0, 8.0, 0.0));
+    /// ```
+    pub fn set_speed(&mut self, speed: f32) {
+        self.0 = self.direction() * speed;
+    }
+}
+
+/// Tints the particles of the [`ParticleSystem`] on the same entity.
+///
+/// The color of each particle is multiplied by this color, on top of its [`ParticleSystem::color`].
+/// This is useful to reuse a single effect for different teams or materials.
+///
+/// The tint is captured when a particle spawns. Without this component, particles are not tinted.
+#[derive(Debug, Component, Clone, Copy, Reflect)]
+#[reflect(Component, Default)]
+pub struct ParentTint(pub Color);
+
+impl Default for ParentTint {
+    fn default() -> Self {
+        Self(Color::WHITE)
+    }
+}
+
+/// Makes the particles of the [`ParticleSystem`] on the same entity drift with the [`ParticleWind`].
+///
+/// The wind is multiplied by this value, so lighter particles such as smoke can be carried further than sparks.
+/// The wind is applied in the space of the particles, so it is relative to the emitter in [`ParticleSpace::Local`].
+///
+/// [`ParticleWind`]: crate::resources::ParticleWind
+#[derive(Debug, Component, Clone, Copy, Reflect)]
+#[reflect(Component, Default)]
+pub struct AffectedByWind(pub f32);
+
+impl Default for AffectedByWind {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+/// Turns the particles of the [`ParticleSystem`] on the same entity into persistent debris, such as the chunks of a
+/// shattered object.
+///
+/// Debris never despawn from old age: their lifetime is infinite, so anything driven by their lifetime stays at its
+/// start, e.g. their color and scale. They are still despawned by ``max_distance``, ``despawn_region`` and
+/// ``despawn_when_offscreen``, and with their system when ``despawn_particles_with_system`` is set. Each one is
+/// marked with [`Debris`], so other systems can add components to them, e.g. for an external physics engine.
+///
+/// Debris count against ``max_particles`` for as long as they live, so a system stops spawning once that many
+/// exist, and a non-looping system never finishes while they are alive. They keep moving with their [`Velocity`]
+/// and velocity modifiers; removing [`Velocity`] from a debris hands its movement over entirely.
+///
+/// ## Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_particle_systems::*;
+/// fn shatter(mut commands: Commands, transform: Transform) {
+///     commands.spawn((
+///         ParticleSystemBundle {
+///             particle_system: ParticleSystem {
+///                 spawn_rate_per_second: 0.0.into(),
+///                 bursts: vec![ParticleBurst::new(0.0, 12)],
+///                 ..ParticleSystem::default()
+///             },
+///             transform,
+///             ..ParticleSystemBundle::default()
+///         },
+///         DebrisMode,
+///         Playing,
+///     ));
+/// }
+///
+/// fn add_physics(debris: Query<Entity, Added<Debris>>, mut commands: Commands) {
+///     for _debris in &debris {
+///         // Insert rigid bodies and colliders here.
+///     }
+/// }
+/// ```
+#[derive(Debug, Default, Component, Clone, Copy, Reflect)]
+#[reflect(Component, Default)]
+pub struct DebrisMode;
+
+/// Marks a particle spawned by a system in [`DebrisMode`].
+#[derive(Debug, Default, Component, Clone, Copy, Reflect)]
+#[reflect(Component, Default)]
+pub struct Debris;
+
+/// Fades the particles of the [`ParticleSystem`] on the same entity where many of them overlap, faking volumetric
+/// depth and keeping dense clusters of additive particles from blowing out to white.
+///
+/// Every frame, the particles of the system are counted on a grid of square cells ``cell_size`` wide, on the `x`
+/// and `y` axes in the space of the particles. The alpha of each particle is then scaled by
+/// `1.0 / count.sqrt()`, raised to the power of ``strength``, where `count` is the number of particles sharing its
+/// cell. A particle alone in its cell is left untouched.
+///
+/// ## Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_particle_systems::*;
+/// fn spawn_smoke(mut commands: Commands) {
+///     commands.spawn((
+///         ParticleSystemBundle::default(),
+///         DensityAlpha::new(16.0, 1.0),
+///         Playing,
+///     ));
+/// }
+/// ```
+#[derive(Debug, Component, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub struct DensityAlpha {
+    /// The width of the grid cells particles are counted in, in the units of the particles' space.
+    ///
+    /// This should be about the size of a particle. A ``cell_size`` that isn't positive disables the fade.
+    pub cell_size: f32,
+
+    /// How strongly dense cells are faded, from `0.0` for no fade to `1.0` for the full `1.0 / count.sqrt()`.
+    pub strength: f32,
+}
+
+impl Default for DensityAlpha {
+    fn default() -> Self {
+        Self::new(10.0, 1.0)
+    }
+}
+
+impl DensityAlpha {
+    /// Creates a new [`DensityAlpha`] counting particles in cells ``cell_size`` wide.
+    pub fn new(cell_size: f32, strength: f32) -> Self {
+        Self {
+            cell_size,
+            strength,
+        }
+    }
+
+    /// The factor the alpha of a particle sharing its cell with ``count`` particles, itself included, is scaled by.
+    ///
+    /// ```
+    /// # use bevy_particle_systems::DensityAlpha;
+    /// let density_alpha = DensityAlpha::new(10.0, 1.0);
+    /// assert_eq!(density_alpha.alpha_scale(1), 1.0);
+    /// assert_eq!(density_alpha.alpha_scale(4), 0.5);
+    /// ```
+    #[allow(clippy::cast_precision_loss)]
+    pub fn alpha_scale(&self, count: usize) -> f32 {
+        if count <= 1 {
+            return 1.0;
+        }
+        (count as f32).powf(-0.5 * self.strength.max(0.0))
+    }
+}
+
+/// Identifies a group of particle systems, see [`ParticleSystemGroup`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Default)]
+pub struct GroupId(pub u32);
+
+/// Adds the [`ParticleSystem`] on the same entity to a group, whose size can be capped with [`GroupLimits`].
+///
+/// This keeps effects spawning a new system for every occurrence, such as footsteps, from accumulating systems.
+/// Once a group grows past its limit, its oldest systems are despawned.
+///
+/// ## Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_particle_systems::*;
+/// const FOOTSTEPS: GroupId = GroupId(0);
+///
+/// fn setup(mut limits: ResMut<GroupLimits>) {
+///     limits.limits.insert(FOOTSTEPS, 8);
+/// }
+///
+/// fn step(mut commands: Commands) {
+///     commands
+///         .spawn(ParticleSystemBundle::default())
+///         .insert((Playing, ParticleSystemGroup(FOOTSTEPS)));
+/// }
+/// ```
+///
+/// [`GroupLimits`]: crate::resources::GroupLimits
+#[derive(Debug, Default, Component, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default)]
+pub struct ParticleSystemGroup(pub GroupId);
+
+/// Decouples the simulation of the [`ParticleSystem`] on the same entity from its presentation.
+///
+/// This is useful for background or off-screen systems: hide a system while it keeps simulating, so it looks
+/// natural when shown again, or freeze it in place while it stays visible. Neither toggle removes [`Playing`].
+///
+/// Without this component, particles are both simulated and rendered.
+#[derive(Debug, Component, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default)]
+pub struct SystemVisibility {
+    /// Whether the system spawns particles and its particles age and move.
+    ///
+    /// When ``false``, the system is frozen: no particles are spawned, its running time doesn't advance and
+    /// existing particles keep their current lifetime, position and velocity, while still being drawn.
+    pub simulate: bool,
+
+    /// Whether the particles of the system are drawn.
+    ///
+    /// When ``false``, particles are made invisible and their colors and texture atlas indices are no longer
+    /// updated, while the simulation keeps running.
+    pub render: bool,
+}
+
+impl Default for SystemVisibility {
+    fn default() -> Self {
+        Self {
+            simulate: true,
+            render: true,
+        }
+    }
+}
+
+/// The companion sprite drawing the outline of a particle, see [`ParticleSystem::outline`].
+///
+/// This lives on a child entity of the [`Particle`] it outlines.
+#[derive(Debug, Component, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct ParticleOutlineSprite {
+    /// The color of the outline, before the alpha of the particle is applied.
+    pub color: Color,
+}
+
+/// A line segment connecting two consecutive particles, see [`ParticleSystem::connect_particles`].
+///
+/// Segments are spawned as top level entities, and are despawned once either particle is gone.
+#[derive(Debug, Component, Clone, Reflect)]
+#[reflect(Component)]
+pub struct ParticleConnection {
+    /// The older particle, spawned right before ``to``.
+    pub from: Entity,
+
+    /// The newer particle.
+    pub to: Entity,
+
+    /// The color of the segment, sampled along the lifetime of ``from``.
+    pub color: ColorOverTime,
+
+    /// The width of the segment, in world units.
+    pub width: f32,
+}
+
+/// The particle a [`SpawnFilter`] is asked about.
+#[derive(Debug, Clone, Copy)]
+pub struct SpawnContext {
+    /// The entity of the [`ParticleSystem`] spawning the particle.
+    pub emitter: Entity,
+
+    /// The position the particle would spawn at, in world space.
+    pub position: Vec3,
+}
+
+/// Lets a function veto the spawning of each particle of the [`ParticleSystem`] on the same entity.
+///
+/// The function is called for every particle about to be spawned, and the particle is skipped when it returns
+/// ``false``, e.g. to stop emitting underwater or while a cooldown runs. Skipped particles are not counted against
+/// ``max_particles`` or ``total_particles_to_emit``, and a filter rejecting every particle simply emits nothing.
+///
+/// The function is called from several threads at once and is skipped by reflection.
+///
+/// ## Examples
+///
+/// ```
+/// # use bevy_particle_systems::SpawnFilter;
+/// // Only spawn particles above the water line.
+/// let above_water = SpawnFilter::new(|context| context.position.y > 0.0);
+/// ```
+#[derive(Component, Clone)]
+pub struct SpawnFilter(pub Arc<dyn Fn(SpawnContext) -> bool + Send + Sync>);
+
+impl SpawnFilter {
+    /// Wraps a function deciding whether each particle may spawn.
+    pub fn new(filter: impl Fn(SpawnContext) -> bool + Send + Sync + 'static) -> Self {
+        Self(Arc::new(filter))
+    }
+}
+
+impl std::fmt::Debug for SpawnFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SpawnFilter")
+    }
 }
 
 /// Marker component indicating that the [`ParticleSystem`] on the same entity is currently Playing.
 #[derive(Debug, Component)]
 pub struct Playing;
 
-/// Tracks running state of the [`ParticleSystem`] on the same entity.
+/// Makes the [`ParticleSystem`] on the same entity emit for a single frame, then stop [`Playing`].
+///
+/// The system's [`RunningState`] and [`BurstIndex`] are reset first, so it fires its bursts at time `0.0` and its
+/// first particle, as if it had just started. The entity is kept afterwards, and inserting `EmitOnce` again fires
+/// it again, which makes it easy to reuse pooled effect entities for impacts and flashes.
 #[derive(Debug, Component, Default, Reflect)]
-#[reflect(Component)]
+#[reflect(Component, Default)]
+pub struct EmitOnce;
+
+/// Moves the playhead of the [`ParticleSystem`] on the same entity to the given time, in seconds, then is removed.
+///
+/// This applies [`ParticleSystem::seek`] before the next particles are spawned, and the system carries on from
+/// there if it is [`Playing`]. Seeking backwards also despawns the particles of the system, so it doesn't show
+/// particles from its future.
+///
+/// ## Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_particle_systems::SeekTo;
+/// fn restart_at_the_climax(mut commands: Commands, effect: Entity) {
+///     commands.entity(effect).insert(SeekTo(2.5));
+/// }
+/// ```
+#[derive(Debug, Component, Default, Clone, Copy, Reflect)]
+#[reflect(Component, Default)]
+pub struct SeekTo(pub f32);
+
+/// Tracks running state of the [`ParticleSystem`] on the same entity.
+#[derive(Debug, Component, Reflect)]
+#[reflect(Component, Default)]
 pub struct RunningState {
     /// Tracks the current amount of time since the start of the system.
     ///
     /// This is reset when the running time surpasses the ``system_duration_seconds``.
     pub running_time: f32,
 
-    /// The truncated current second.
-    pub current_second: f32,
+    /// The fractional number of particles owed by the spawn rate, carried between frames.
+    ///
+    /// Each frame adds ``spawn_rate_per_second`` times the elapsed time, and whole particles are spawned from it.
+    /// Frames longer than a quarter of a second, such as the first one after a pause, only count as a quarter of a
+    /// second, so they don't spawn a large batch at once. This starts at `1.0` so the first particle is emitted as soon as the system starts playing.
+    pub spawn_accumulator: f32,
+
+    /// The total number of particles spawned since the system started, including bursts.
+    ///
+    /// This is never reset when looping, and is compared against ``total_particles_to_emit``.
+    pub total_spawned: usize,
+
+    /// Particles requested outside of the spawn rate and bursts, such as by a [`BeatEmitter`] or a
+    /// [`ParticleBurstRequest`].
+    ///
+    /// [`ParticleBurstRequest`]: crate::events::ParticleBurstRequest
+    ///
+    /// They are spawned on the next frame the system runs, and this is reset to `0`. Nothing is added while the
+    /// system isn't [`Playing`].
+    pub requested_particles: usize,
+
+    /// The number of [`RepeatingBurst`]s fired since the system started or last looped.
+    pub repeating_bursts_fired: usize,
+
+    /// The number of times the system has looped back to the start, compared against
+    /// [`ParticleSystem::loop_count`].
+    pub completed_loops: u32,
+
+    /// Burst particles waiting for room to spawn, see [`ParticleSystem::deferred_bursts`].
+    pub deferred_burst_particles: usize,
+
+    /// How long, in seconds, the ``deferred_burst_particles`` have been waiting.
+    pub deferred_burst_wait: f32,
+
+    /// Particles held back by [`ParticleSystem::max_spawns_per_frame`], spawned on the following frames.
+    pub pending_spawns: usize,
+
+    /// The number of generators drawn from the [`ParticleSystem::seed`] since the system started.
+    ///
+    /// Each frame that samples random values advances this, so a seeded system carries on with a new stream even
+    /// on frames where nothing was spawned. It is reset when the system restarts, which replays the same particles.
+    pub seeded_draws: u64,
+
+    /// The warnings already logged for the system, so each is only logged once.
+    #[reflect(ignore)]
+    warned: SystemWarnings,
+
+    /// The most recently spawned particle, which the next particle is connected to when using
+    /// [`ParticleSystem::connect_particles`].
+    pub last_spawned_particle: Option<Entity>,
+
+    /// The emitter's world position on the previous frame, used by
+    /// [`ParticleSystem::interpolate_spawn_positions`].
+    pub previous_position: Option<Vec3>,
+
+    /// The time and count of each of [`ParticleSystem::bursts`], sorted by time and indexed by [`BurstIndex`].
+    ///
+    /// Relative burst times are resolved against ``system_duration_seconds``. This is rebuilt whenever the
+    /// [`ParticleSystem`] changes, keeping list order for bursts sharing a time.
+    pub burst_order: Vec<(f32, usize)>,
+}
+
+impl RunningState {
+    /// Logs the message built by ``message``, unless ``warning`` was already logged for the system.
+    pub(crate) fn warn_once(&mut self, warning: SystemWarning, message: impl FnOnce() -> String) {
+        if self.warned.insert(warning) {
+            warn!("{}", message());
+        }
+    }
+
+    /// Whether ``warning`` was already logged for the system.
+    #[cfg(test)]
+    pub(crate) fn has_warned(&self, warning: SystemWarning) -> bool {
+        self.warned.contains(warning)
+    }
+
+    /// Starts the system over, keeping track of the warnings already logged for it.
+    pub(crate) fn restart(&mut self) {
+        *self = Self {
+            warned: self.warned,
+            ..Self::default()
+        };
+    }
+
+    /// Rebuilds ``burst_order`` from the bursts of ``particle_system``.
+    pub(crate) fn resolve_burst_order(&mut self, particle_system: &ParticleSystem) {
+        self.burst_order.clear();
+        self.burst_order
+            .extend(particle_system.bursts.iter().map(|burst| {
+                (
+                    burst.resolved_time(particle_system.system_duration_seconds),
+                    burst.count,
+                )
+            }));
+        self.burst_order.sort_by(|a, b| a.0.total_cmp(&b.0));
+    }
+}
+
+/// A warning about a misconfigured [`ParticleSystem`], logged once per system by [`RunningState::warn_once`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SystemWarning {
+    /// The system has no texture set.
+    MissingTexture,
+    /// The system sampled a lifetime too short to be used.
+    ShortLifetime,
+    /// The system has more ``min_particles`` than ``max_particles``.
+    MinParticles,
+    /// The system has an ``axis_scale`` of zero on x or y.
+    ZeroAxisScale,
+}
+
+/// The set of [`SystemWarning`]s already logged for a system.
+#[derive(Debug, Clone, Copy, Default)]
+struct SystemWarnings(u8);
+
+impl SystemWarnings {
+    /// Adds ``warning`` to the set, returning whether it wasn't in it yet.
+    fn insert(&mut self, warning: SystemWarning) -> bool {
+        let inserted = !self.contains(warning);
+        self.0 |= 1 << warning as u8;
+        inserted
+    }
+
+    fn contains(self, warning: SystemWarning) -> bool {
+        self.0 & (1 << warning as u8) != 0
+    }
+}
+
+impl Default for RunningState {
+    fn default() -> Self {
+        Self {
+            running_time: 0.0,
+            spawn_accumulator: 1.0,
+            total_spawned: 0,
+            requested_particles: 0,
+            repeating_bursts_fired: 0,
+            completed_loops: 0,
+            deferred_burst_particles: 0,
+            deferred_burst_wait: 0.0,
+            pending_spawns: 0,
+            seeded_draws: 0,
+            warned: SystemWarnings::default(),
+            last_spawned_particle: None,
+            previous_position: None,
+            burst_order: Vec::new(),
+        }
+    }
+}
 
-    /// The number of particles already spawned during ``current_second``.
+/// Fires a burst of particles at a fixed interval from the [`ParticleSystem`] on the same entity.
+///
+/// Beats are independent of ``system_duration_seconds`` and ``bursts``, which makes this useful to sync bursts to
+/// music. Beats only advance while the system is [`Playing`], using the system's ``use_scaled_time`` setting.
+///
+/// ## Examples
+///
+/// ```
+/// # use bevy_particle_systems::BeatEmitter;
+/// // 120 BPM, 10 particles per beat, starting half a second in.
+/// let beat_emitter = BeatEmitter::new(0.5, 10).with_offset(0.5);
+/// ```
+#[derive(Debug, Component, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub struct BeatEmitter {
+    /// The time between beats, in seconds.
     ///
-    /// This number is reset when ``current_second`` rolls over.
-    pub spawned_this_second: usize,
+    /// This can be changed at runtime; the next beat keeps its scheduled time and the following ones use the new
+    /// interval. Beats are disabled while this is not positive.
+    pub interval: f32,
+
+    /// How many particles are emitted on each beat.
+    pub count: usize,
+
+    /// Time remaining until the next beat, in seconds.
+    pub(crate) next_beat_in: f32,
+}
+
+impl BeatEmitter {
+    /// Creates a new `BeatEmitter` firing `count` particles every `interval` seconds, starting immediately.
+    pub fn new(interval: f32, count: usize) -> Self {
+        Self {
+            interval,
+            count,
+            next_beat_in: 0.0,
+        }
+    }
+
+    /// Delays the first beat by `offset` seconds.
+    pub fn with_offset(mut self, offset: f32) -> Self {
+        self.next_beat_in = offset;
+        self
+    }
+}
+
+impl Default for BeatEmitter {
+    fn default() -> Self {
+        Self::new(1.0, 1)
+    }
+}
+
+/// Smoothly transitions the [`ParticleSystem`] on the same entity towards ``target`` over ``duration`` seconds.
+///
+/// The following values are interpolated every frame, from the system's values when the tween started:
+/// - ``max_particles``, ``system_duration_seconds``, and ``max_distance`` and ``direction_spread`` when both are set.
+/// - ``spawn_rate_per_second``, ``scale`` and ``color`` when both the start and target values are constants.
+/// - The base value of ``initial_speed``, ``lifetime``, ``initial_scale``, ``rotation_speed``, and
+///   ``initial_rotation`` when both are jittered, along with their jitter ranges when both have one.
+///
+/// Every other value, such as the texture, emitter shape or curves, snaps to ``target`` once the tween finishes.
+/// The tween then removes itself. Time advances according to the system's ``use_scaled_time`` setting.
+///
+/// ## Examples
+///
+/// ```
+/// # use bevy_particle_systems::{ParticleSystem, ParticleSystemTween};
+/// // Grow a small fire into an inferno over three seconds.
+/// let inferno = ParticleSystem {
+///     spawn_rate_per_second: 500.0.into(),
+///     initial_speed: 120.0.into(),
+///     ..ParticleSystem::default()
+/// };
+/// let tween = ParticleSystemTween::new(inferno, 3.0);
+/// ```
+#[derive(Debug, Component, Clone)]
+pub struct ParticleSystemTween {
+    /// The parameters the system transitions to.
+    pub target: Box<ParticleSystem>,
+
+    /// How long, in seconds, the transition takes.
+    pub duration: f32,
+
+    /// How long, in seconds, the transition has been running.
+    pub elapsed: f32,
+
+    /// The parameters of the system when the tween started, captured on its first frame.
+    pub(crate) start: Option<Box<ParticleSystem>>,
+}
+
+impl ParticleSystemTween {
+    /// Creates a new [`ParticleSystemTween`] transitioning to ``target`` over ``duration`` seconds.
+    pub fn new(target: ParticleSystem, duration: f32) -> Self {
+        Self {
+            target: Box::new(target),
+            duration,
+            elapsed: 0.0,
+            start: None,
+        }
+    }
+
+    /// How far along the transition is, from `0.0` to `1.0`.
+    ///
+    /// A ``duration`` of `0.0` or less finishes the transition immediately.
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0.0 {
+            1.0
+        } else {
+            (self.elapsed / self.duration).clamp(0.0, 1.0)
+        }
+    }
+}
+
+/// Scales the spawn rate of the [`ParticleSystem`] on the same entity down as it gets further from the camera.
+///
+/// The spawn rate and burst counts are at full strength up to ``near`` world units from the nearest active camera,
+/// then scale down linearly to ``min_rate_scale`` at ``far``. This stacks with [`ParticleQualitySettings`].
+/// Systems are left untouched while there is no active camera.
+///
+/// [`ParticleQualitySettings`]: crate::resources::ParticleQualitySettings
+///
+/// ## Examples
+///
+/// ```
+/// # use bevy_particle_systems::DistanceLod;
+/// // Full rate within 50 units, a quarter of it at 400 units, and nothing beyond.
+/// let lod = DistanceLod::new(50.0, 400.0, 0.25).with_cull_beyond_far();
+/// ```
+#[derive(Debug, Component, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub struct DistanceLod {
+    /// The distance, in world units, up to which the system emits at its full rate.
+    pub near: f32,
+
+    /// The distance, in world units, at which the rate is scaled by ``min_rate_scale``.
+    pub far: f32,
+
+    /// The scale applied to the spawn rate at ``far`` and beyond.
+    pub min_rate_scale: f32,
+
+    /// Whether the system stops emitting entirely beyond ``far``, rather than emitting at ``min_rate_scale``.
+    pub cull_beyond_far: bool,
+
+    /// The scale computed for the current frame.
+    pub(crate) rate_scale: f32,
+}
+
+impl DistanceLod {
+    /// Creates a new [`DistanceLod`] scaling from full rate at ``near`` down to ``min_rate_scale`` at ``far``.
+    pub fn new(near: f32, far: f32, min_rate_scale: f32) -> Self {
+        Self {
+            near,
+            far,
+            min_rate_scale,
+            cull_beyond_far: false,
+            rate_scale: 1.0,
+        }
+    }
+
+    /// Stops the system from emitting beyond ``far``.
+    pub fn with_cull_beyond_far(mut self) -> Self {
+        self.cull_beyond_far = true;
+        self
+    }
+
+    /// The scale applied to the spawn rate on the current frame, or `1.0` before the system first played.
+    pub fn rate_scale(&self) -> f32 {
+        self.rate_scale
+    }
+
+    /// The scale for an emitter ``distance`` world units away from the camera.
+    pub fn rate_scale_at(&self, distance: f32) -> f32 {
+        if distance <= self.near {
+            1.0
+        } else if distance >= self.far {
+            if self.cull_beyond_far {
+                0.0
+            } else {
+                self.min_rate_scale
+            }
+        } else {
+            let pct = (distance - self.near) / (self.far - self.near);
+            1.0 + (self.min_rate_scale - 1.0) * pct
+        }
+    }
+}
+
+impl Default for DistanceLod {
+    fn default() -> Self {
+        Self::new(100.0, 1000.0, 0.1)
+    }
 }
 
 /// Tracks the current particle count for the [`ParticleSystem`] on the same entity.
 #[derive(Debug, Component, Default, Reflect)]
-#[reflect(Component)]
+#[reflect(Component, Default)]
 pub struct ParticleCount(pub usize);
 
+/// Opts the [`ParticleSystem`] on the same entity into collecting the positions of its particles every frame.
+///
+/// A [`ParticlePositions`] component is added to the entity, and refreshed once the frame's particles have spawned
+/// and moved, e.g. to pan a sound towards a swarm or check which sparks are near the player.
+#[derive(Debug, Component, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct CollectParticlePositions;
+
+/// The world positions of the live particles of the [`ParticleSystem`] on the same entity, in no particular order.
+///
+/// This is only kept up to date with [`CollectParticlePositions`]. The `Vec` is cleared and refilled every frame,
+/// so it keeps its allocation.
+#[derive(Debug, Component, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct ParticlePositions(pub Vec<Vec3>);
+
 /// Tracks the current index for particle bursts for the [`ParticleSystem`] on the same entity.
 #[derive(Debug, Component, Default, Reflect)]
-#[reflect(Component)]
+#[reflect(Component, Default)]
 pub struct BurstIndex(pub usize);
 
 /// A spawnable bundle for a [`ParticleSystem`] containing all of the necessary components.
@@ -0,0 +1,73 @@
+//! Defines bevy Events sent by the particle systems.
+
+use bevy_ecs::prelude::{Entity, Event};
+use bevy_reflect::prelude::*;
+
+/// Sent when a particle crosses one of the [`crate::components::ParticleSystem::lifetime_events`] of its system.
+///
+/// This allows reacting to particles partway through their life, e.g. spawning smoke from a rocket once it has
+/// lived half of its lifetime. Events are sent in the order of the thresholds, and a single frame crossing several
+/// thresholds sends one event for each.
+///
+/// ## Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_particle_systems::ParticleLifetimeThreshold;
+/// fn start_smoking(mut thresholds: EventReader<ParticleLifetimeThreshold>) {
+///     for event in thresholds.read() {
+///         info!("{} reached {}% of its lifetime", event.entity, event.threshold * 100.0);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Event, Reflect)]
+pub struct ParticleLifetimeThreshold {
+    /// The particle crossing the threshold.
+    pub entity: Entity,
+
+    /// The fraction of its lifetime the particle crossed, as configured in ``lifetime_events``.
+    pub threshold: f32,
+}
+
+/// Requests the [`crate::components::ParticleSystem`] on ``entity`` to emit ``count`` extra particles at once.
+///
+/// This fires bursts in reaction to gameplay, such as a collision reported by a physics crate, without the
+/// particle systems depending on it. Requests sent to the same system in one frame add up, and are spawned on top
+/// of the spawn rate and bursts the next time the system emits, scaled by the
+/// [`crate::resources::ParticleQualitySettings`]. Requests for entities without a particle system are ignored.
+///
+/// Requests for a system that isn't [`crate::components::Playing`] are dropped rather than queued, so pausing a
+/// system doesn't store up a burst that fires all at once when it resumes.
+///
+/// ## Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_particle_systems::ParticleBurstRequest;
+/// // An event sent by the physics engine, or any other gameplay system.
+/// #[derive(Event)]
+/// struct Collision {
+///     emitter: Entity,
+///     impact_speed: f32,
+/// }
+///
+/// fn sparks_on_collision(
+///     mut collisions: EventReader<Collision>,
+///     mut bursts: EventWriter<ParticleBurstRequest>,
+/// ) {
+///     for collision in collisions.read() {
+///         bursts.send(ParticleBurstRequest {
+///             entity: collision.emitter,
+///             count: (collision.impact_speed / 10.0) as usize,
+///         });
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event, Reflect)]
+pub struct ParticleBurstRequest {
+    /// The particle system emitting the burst.
+    pub entity: Entity,
+
+    /// The number of particles to emit.
+    pub count: usize,
+}
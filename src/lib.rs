@@ -10,6 +10,12 @@
 //!
 //! Currently sprite based and focused on 2D.
 //!
+//! ## Features
+//!
+//! - `sequential`: update particles on a single thread rather than Bevy's compute task pool. This is always
+//!   enabled when building for `wasm32`, where parallel iteration only adds overhead.
+//! - `custom_color`: adds [`ColorOverTime::Custom`], to compute particle colors with a function of their state.
+//!
 //! ## Usage
 //!
 //! 1. Add the [`ParticleSystemPlugin`] plugin.
@@ -61,22 +67,34 @@
 //! ```
 //!
 pub mod components;
+pub mod events;
+pub mod resources;
 mod systems;
 pub mod values;
 
+use std::ops::Range;
+
 use bevy_app::{
     prelude::{App, Plugin},
-    Update,
+    PostUpdate, Update,
 };
 use bevy_color::Color;
-use bevy_ecs::prelude::IntoSystemConfigs;
+use bevy_ecs::prelude::{resource_exists, IntoSystemConfigs};
 use bevy_math::Vec3;
 use bevy_reflect::std_traits::ReflectDefault;
+use bevy_render::view::VisibilitySystems;
+use bevy_transform::TransformSystem;
 pub use components::*;
+pub use events::*;
+pub use resources::*;
 pub use systems::ParticleSystemSet;
 use systems::{
-    particle_cleanup, particle_lifetime, particle_spawner, particle_sprite_color,
-    particle_texture_atlas_index, particle_transform,
+    particle_beat_emitter, particle_burst_requests, particle_cleanup, particle_collect_positions,
+    particle_connections, particle_count_reconciliation, particle_density_alpha,
+    particle_distance_lod, particle_emit_once_end, particle_emit_once_start, particle_group_limits,
+    particle_lifetime, particle_lifetime_events, particle_mesh_samplers, particle_outline,
+    particle_seek, particle_spawner, particle_sprite_color, particle_system_stats,
+    particle_system_tween, particle_texture_atlas_index, particle_transform, MeshSurfaceSamplers,
 };
 pub use values::*;
 
@@ -104,34 +122,138 @@ impl Plugin for ParticleSystemPlugin {
         app.add_systems(
             Update,
             (
-                particle_spawner,
+                particle_beat_emitter,
+                particle_system_tween,
                 particle_lifetime,
+                particle_lifetime_events.after(particle_lifetime),
                 particle_sprite_color,
+                particle_density_alpha.after(particle_sprite_color),
                 particle_texture_atlas_index,
-                particle_transform,
+                particle_outline
+                    .after(particle_density_alpha)
+                    .after(particle_texture_atlas_index),
+                particle_transform.after(particle_lifetime),
                 particle_cleanup,
             )
                 .into_configs()
                 .in_set(ParticleSystemSet),
         );
-        app.register_type::<Curve<f32>>()
-            .register_type::<Curve<Vec3>>()
-            .register_type::<Curve<Color>>()
-            .register_type::<Lerp<f32>>()
-            .register_type_data::<Lerp<f32>, ReflectDefault>()
-            .register_type::<Lerp<Vec3>>()
-            .register_type_data::<Lerp<Vec3>, ReflectDefault>()
-            .register_type::<Lerp<Color>>()
-            .register_type_data::<Lerp<Color>, ReflectDefault>()
-            .register_type::<ValueOverTime>()
-            .register_type::<VectorOverTime>()
-            .register_type::<ColorOverTime>()
-            .register_type::<VelocityModifier>()
-            .register_type::<Noise2D>()
-            .register_type::<SinWave>()
-            .register_type::<ParticleSystem>()
-            .register_type::<ParticleCount>()
-            .register_type::<RunningState>()
-            .register_type::<BurstIndex>();
+        app.add_systems(
+            PostUpdate,
+            (
+                particle_group_limits,
+                particle_emit_once_start,
+                particle_burst_requests,
+                particle_seek,
+                // Runs once the previous frame's spawns and this frame's despawns have been applied.
+                particle_count_reconciliation.run_if(resource_exists::<ReconcileParticleCounts>),
+                particle_distance_lod,
+                particle_mesh_samplers,
+                particle_spawner,
+                particle_emit_once_end,
+                particle_connections,
+                particle_collect_positions,
+                particle_system_stats.run_if(resource_exists::<ParticleSystemStats>),
+            )
+                .chain()
+                .in_set(ParticleSystemSet)
+                .after(TransformSystem::TransformPropagate)
+                .before(VisibilitySystems::VisibilityPropagate),
+        );
+        app.add_event::<ParticleLifetimeThreshold>()
+            .add_event::<ParticleBurstRequest>();
+        app.init_resource::<ParticleQualitySettings>()
+            .init_resource::<GlobalParticleGravity>()
+            .init_resource::<ParticleWind>()
+            .init_resource::<ParticleTemplates>()
+            .init_resource::<GroupLimits>()
+            .init_resource::<MeshSurfaceSamplers>();
+        register_types(app);
     }
 }
+
+/// Registers every reflected type of the crate, so they can be used in scenes and inspectors.
+fn register_types(app: &mut App) {
+    app.register_type::<Curve<f32>>()
+        .register_type::<Curve<Vec3>>()
+        .register_type::<Curve<Color>>()
+        .register_type::<CurvePoint<f32>>()
+        .register_type_data::<CurvePoint<f32>, ReflectDefault>()
+        .register_type::<CurvePoint<Vec3>>()
+        .register_type_data::<CurvePoint<Vec3>, ReflectDefault>()
+        .register_type::<CurvePoint<Color>>()
+        .register_type_data::<CurvePoint<Color>, ReflectDefault>()
+        .register_type::<RandomValue<usize>>()
+        .register_type_data::<RandomValue<usize>, ReflectDefault>()
+        .register_type::<RandomValue<Vec3>>()
+        .register_type_data::<RandomValue<Vec3>, ReflectDefault>()
+        .register_type::<Range<f32>>()
+        .register_type_data::<Range<f32>, ReflectDefault>()
+        .register_type::<Lerp<f32>>()
+        .register_type_data::<Lerp<f32>, ReflectDefault>()
+        .register_type::<Lerp<Vec3>>()
+        .register_type_data::<Lerp<Vec3>, ReflectDefault>()
+        .register_type::<Lerp<Color>>()
+        .register_type_data::<Lerp<Color>, ReflectDefault>()
+        .register_type::<JitteredValue>()
+        .register_type::<InitialRotation>()
+        .register_type::<InitialRotationMode>()
+        .register_type::<ValueOverTime>()
+        .register_type::<VectorOverTime>()
+        .register_type::<ColorOverTime>()
+        .register_type::<ColorBlendOp>()
+        .register_type::<ColorDriver>()
+        .register_type::<AlphaOverTime>()
+        .register_type::<VelocityModifier>()
+        .register_type::<VelocityConstraint>()
+        .register_type::<FlickerModifier>()
+        .register_type::<EmissionControl>()
+        .register_type::<LoopCount>()
+        .register_type::<Noise2D>()
+        .register_type::<SinWave>()
+        .register_type::<EmitterShape>()
+        .register_type::<CircleSegment>()
+        .register_type::<Line>()
+        .register_type::<ParticleTexture>()
+        .register_type::<AtlasIndex>()
+        .register_type::<AnimatedIndex>()
+        .register_type::<DistanceAnimatedIndex>()
+        .register_type::<ParticleBurst>()
+        .register_type::<RepeatingBurst>()
+        .register_type::<ParticleSpace>()
+        .register_type::<ParticleSystem>()
+        .register_type::<ParticleCount>()
+        .register_type::<CollectParticlePositions>()
+        .register_type::<ParticlePositions>()
+        .register_type::<RunningState>()
+        .register_type::<BurstIndex>()
+        .register_type::<BeatEmitter>()
+        .register_type::<DistanceLod>()
+        .register_type::<EmitOnce>()
+        .register_type::<OffscreenDespawn>()
+        .register_type::<ParticleRegion>()
+        .register_type::<ParticleOutline>()
+        .register_type::<ParticleOutlineSprite>()
+        .register_type::<ConnectParticles>()
+        .register_type::<ParticleConnection>()
+        .register_type::<ParentTint>()
+        .register_type::<SystemVisibility>()
+        .register_type::<ParticleQualitySettings>()
+        .register_type::<GlobalParticleGravity>()
+        .register_type::<ParticleWind>()
+        .register_type::<AffectedByWind>()
+        .register_type::<DebrisMode>()
+        .register_type::<Debris>()
+        .register_type::<DensityAlpha>()
+        .register_type::<SeekTo>()
+        .register_type::<GroupId>()
+        .register_type::<ParticleSystemGroup>()
+        .register_type::<ReconcileParticleCounts>()
+        .register_type::<ParticleSystemStats>()
+        .register_type::<ParticleSystemStat>()
+        .register_type::<ParticleTemplates>()
+        .register_type::<GroupLimits>()
+        .register_type::<LifetimeThresholds>()
+        .register_type::<ParticleLifetimeThreshold>()
+        .register_type::<ParticleBurstRequest>();
+}
@@ -0,0 +1,273 @@
+//! Defines bevy Resources used to configure and inspect all particle systems at once.
+
+use std::collections::HashMap;
+
+use bevy_ecs::prelude::{Commands, Entity, ReflectResource, Resource};
+use bevy_log::error;
+use bevy_math::{Vec2, Vec3};
+use bevy_reflect::prelude::*;
+use bevy_transform::prelude::Transform;
+
+use crate::{
+    components::{GroupId, ParticleSystem, ParticleSystemBundle, Playing},
+    values::Noise2D,
+};
+
+/// Global settings scaling the activity of every [`crate::components::ParticleSystem`].
+///
+/// This is intended to back options such as a "Particle Quality" setting, or reduced motion for accessibility.
+/// A scale of `0.0` disables particles entirely, while `0.5` halves their density.
+///
+/// ## Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_particle_systems::ParticleQualitySettings;
+/// fn set_low_quality(mut quality: ResMut<ParticleQualitySettings>) {
+///     quality.spawn_rate_scale = 0.5;
+///     quality.max_particles_scale = 0.5;
+/// }
+/// ```
+#[derive(Debug, Clone, Resource, Reflect)]
+#[reflect(Resource, Default)]
+pub struct ParticleQualitySettings {
+    /// Multiplies the spawn rate and burst counts of every system.
+    pub spawn_rate_scale: f32,
+
+    /// Multiplies the ``max_particles`` of every system.
+    pub max_particles_scale: f32,
+}
+
+impl Default for ParticleQualitySettings {
+    fn default() -> Self {
+        Self {
+            spawn_rate_scale: 1.0,
+            max_particles_scale: 1.0,
+        }
+    }
+}
+
+impl ParticleQualitySettings {
+    /// Scales a particle count by ``scale``, rounding to the nearest whole particle.
+    #[allow(
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation
+    )]
+    pub(crate) fn scale_count(count: usize, scale: f32) -> usize {
+        (count as f32 * scale.max(0.0)).round() as usize
+    }
+}
+
+/// A gravity shared by every particle using [`crate::values::VelocityModifier::GlobalGravity`].
+///
+/// Changing this at runtime, such as for a zero gravity power-up, instantly affects all of those particles.
+/// Defaults to [`Vec3::ZERO`], so the modifier has no effect until this is set.
+#[derive(Debug, Clone, Copy, Default, Resource, Reflect)]
+#[reflect(Resource, Default)]
+pub struct GlobalParticleGravity(pub Vec3);
+
+/// A wind carrying the particles of every system with an [`crate::components::AffectedByWind`] component.
+///
+/// Every frame, those particles are moved by ``velocity`` on top of their own velocity, plus some noise based
+/// turbulence. Changing this at runtime, such as for gusts, instantly affects all of them. Defaults to no wind.
+///
+/// ## Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_particle_systems::ParticleWind;
+/// fn gust(mut wind: ResMut<ParticleWind>, time: Res<Time>) {
+///     wind.velocity = Vec3::new(40.0 + 30.0 * time.elapsed_seconds().sin(), 0.0, 0.0);
+///     wind.turbulence = 10.0;
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default, Resource, Reflect)]
+#[reflect(Resource, Default)]
+pub struct ParticleWind {
+    /// The velocity the wind carries particles at, in world units per second.
+    pub velocity: Vec3,
+
+    /// How much the wind swirls, adding up to this many world units per second along the x and y axes.
+    pub turbulence: f32,
+}
+
+impl ParticleWind {
+    const TURBULENCE: Noise2D = Noise2D {
+        frequency: 0.05,
+        amplitude: 0.5,
+        translation: Vec2::new(10.0, 8.5),
+    };
+
+    /// The velocity the wind carries a particle at, at the given position and time.
+    pub fn velocity_at(&self, position: Vec3, time: f32) -> Vec3 {
+        if self.turbulence == 0.0 {
+            return self.velocity;
+        }
+        let turbulence = Self::TURBULENCE.sample(position.truncate(), time) * self.turbulence;
+        self.velocity + turbulence.extend(0.0)
+    }
+}
+
+/// Recounts the live particles of every system each frame while this resource exists, correcting any drift in
+/// [`crate::components::ParticleCount`].
+///
+/// Counts can drift when particles are despawned by other code, which would otherwise throttle spawning for the
+/// rest of the system's life. Recounting visits every particle each frame, so this is not enabled by default.
+///
+/// ## Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_particle_systems::ReconcileParticleCounts;
+/// fn enable_reconciliation(mut commands: Commands) {
+///     commands.insert_resource(ReconcileParticleCounts);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default, Resource, Reflect)]
+#[reflect(Resource, Default)]
+pub struct ReconcileParticleCounts;
+
+/// A summary of every [`crate::components::ParticleSystem`], refreshed each frame while this resource exists.
+///
+/// This is intended for debug overlays and profiling, and saves joining the system's components by hand. Systems
+/// that are not [`crate::components::Playing`] are still listed. Since it visits every system each frame, this is
+/// not enabled by default.
+///
+/// ## Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_particle_systems::ParticleSystemStats;
+/// fn enable_stats(mut commands: Commands) {
+///     commands.init_resource::<ParticleSystemStats>();
+/// }
+///
+/// fn log_stats(stats: Res<ParticleSystemStats>) {
+///     for stat in &stats.systems {
+///         info!("{}: {} particles, {:.0}%", stat.entity, stat.count, stat.progress * 100.0);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Resource, Reflect)]
+#[reflect(Resource, Default)]
+pub struct ParticleSystemStats {
+    /// One entry per particle system, in no particular order.
+    pub systems: Vec<ParticleSystemStat>,
+}
+
+/// The state of a single [`crate::components::ParticleSystem`], see [`ParticleSystemStats`].
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct ParticleSystemStat {
+    /// The entity the system is on.
+    pub entity: Entity,
+
+    /// The number of live particles spawned by the system.
+    pub count: usize,
+
+    /// Whether the system is [`crate::components::Playing`].
+    pub playing: bool,
+
+    /// Whether the system loops.
+    pub looping: bool,
+
+    /// How far through its ``system_duration_seconds`` the system is, from `0.0` to `1.0`.
+    ///
+    /// See [`crate::components::ParticleSystem::progress`].
+    pub progress: f32,
+}
+
+/// Named [`ParticleSystem`] configurations, to define a project's effects in one place and spawn them by name.
+///
+/// ## Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_particle_systems::{ParticleSystem, ParticleTemplates};
+/// fn register_templates(mut templates: ResMut<ParticleTemplates>) {
+///     templates.insert(
+///         "explosion",
+///         ParticleSystem {
+///             max_particles: 500,
+///             spawn_rate_per_second: 0.0.into(),
+///             ..ParticleSystem::oneshot()
+///         },
+///     );
+/// }
+///
+/// fn explode(mut commands: Commands, templates: Res<ParticleTemplates>) {
+///     templates.spawn(&mut commands, "explosion", Transform::from_xyz(0.0, 100.0, 0.0));
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Resource, Reflect)]
+#[reflect(Resource, Default)]
+pub struct ParticleTemplates {
+    templates: HashMap<String, ParticleSystem>,
+}
+
+impl ParticleTemplates {
+    /// Registers a template under the given name, returning the template it replaced, if any.
+    pub fn insert(
+        &mut self,
+        name: impl Into<String>,
+        particle_system: ParticleSystem,
+    ) -> Option<ParticleSystem> {
+        self.templates.insert(name.into(), particle_system)
+    }
+
+    /// The template registered under the given name.
+    pub fn get(&self, name: &str) -> Option<&ParticleSystem> {
+        self.templates.get(name)
+    }
+
+    /// Removes the template registered under the given name, returning it.
+    pub fn remove(&mut self, name: &str) -> Option<ParticleSystem> {
+        self.templates.remove(name)
+    }
+
+    /// Spawns a copy of the named template at ``transform``, already [`Playing`].
+    ///
+    /// If no template is registered under that name, an error is logged and nothing is spawned.
+    pub fn spawn(
+        &self,
+        commands: &mut Commands,
+        name: &str,
+        transform: Transform,
+    ) -> Option<Entity> {
+        let Some(particle_system) = self.get(name) else {
+            error!("No particle system template named \"{name}\" was registered");
+            return None;
+        };
+        let entity = commands
+            .spawn(ParticleSystemBundle {
+                particle_system: particle_system.clone(),
+                transform,
+                ..ParticleSystemBundle::default()
+            })
+            .insert(Playing)
+            .id();
+        Some(entity)
+    }
+}
+
+/// Caps how many particle systems of each [`ParticleSystemGroup`] can exist at once.
+///
+/// When a group grows past its limit, its oldest systems are despawned. Only the systems are despawned: their
+/// particles live out their lifetime, unless [`ParticleSystem::despawn_particles_with_system`] is set. Groups
+/// without a limit can grow without bound.
+///
+/// [`ParticleSystemGroup`]: crate::components::ParticleSystemGroup
+#[derive(Debug, Clone, Default, Resource, Reflect)]
+#[reflect(Resource, Default)]
+pub struct GroupLimits {
+    /// The maximum number of systems alive in each group.
+    pub limits: HashMap<GroupId, usize>,
+}
+
+impl GroupLimits {
+    /// Limits ``group`` to ``max_systems`` systems alive at once.
+    pub fn with_limit(mut self, group: GroupId, max_systems: usize) -> Self {
+        self.limits.insert(group, max_systems);
+        self
+    }
+}
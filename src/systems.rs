@@ -1,28 +1,131 @@
-use bevy_asset::Handle;
-use bevy_ecs::prelude::{Commands, Entity, Query, Res, SystemSet, With};
-use bevy_hierarchy::BuildChildren;
-use bevy_math::{Quat, Vec2, Vec3};
-use bevy_render::texture::Image;
+use std::collections::{HashMap, VecDeque};
+use std::f32::consts::{PI, TAU};
+
+use bevy_asset::{AssetEvent, AssetId, Assets, Handle};
+use bevy_color::{Alpha, Color, LinearRgba};
+use bevy_ecs::{
+    entity::{EntityHashMap, EntityHashSet},
+    event::ManualEventReader,
+    prelude::{
+        Added, Changed, Commands, DetectChanges, DetectChangesMut, Entity, EventReader,
+        EventWriter, Events, Has, Local, Query, Ref, Res, ResMut, Resource, SystemSet, With,
+        Without,
+    },
+    query::{QueryData, QueryFilter, QueryItem},
+    system::EntityCommands,
+};
+use bevy_hierarchy::{BuildChildren, ChildBuilder, DespawnRecursiveExt, Parent};
+use bevy_log::warn_once;
+use bevy_math::{IVec2, Quat, Vec2, Vec3};
+use bevy_render::{
+    camera::Camera,
+    mesh::Mesh,
+    primitives::{Frustum, Sphere},
+    view::Visibility,
+};
 use bevy_sprite::prelude::{Sprite, SpriteBundle, TextureAtlas};
 use bevy_time::{Real, Time};
 use bevy_transform::prelude::{GlobalTransform, Transform};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
 use crate::{
     components::{
-        BurstIndex, Lifetime, Particle, ParticleBundle, ParticleColor, ParticleCount,
-        ParticleSpace, ParticleSystem, Playing, RunningState, Velocity,
+        AffectedByWind, BeatEmitter, BurstIndex, CollectParticlePositions, ConnectParticles,
+        Debris, DebrisMode, DensityAlpha, DistanceLod, EmitOnce, GroupId, Lifetime,
+        LifetimeThresholds, ParentTint, Particle, ParticleBundle, ParticleColor,
+        ParticleConnection, ParticleCount, ParticleOutline, ParticleOutlineSprite,
+        ParticlePositions, ParticleSpace, ParticleSystem, ParticleSystemGroup, ParticleSystemTween,
+        Playing, RunningState, SeekTo, SpawnContext, SpawnFilter, SystemVisibility, SystemWarning,
+        Velocity,
     },
-    values::{ColorOverTime, PrecalculatedParticleVariables, VelocityModifier},
-    DistanceTraveled, ParticleTexture,
+    events::{ParticleBurstRequest, ParticleLifetimeThreshold},
+    values::{
+        ColorContext, ColorOverTime, EmissionControl, EmitterShape, InitialRotation, JitteredValue,
+        Lerpable, MeshSurfaceSampler, PrecalculatedParticleVariables, ValueOverTime,
+        VelocityModifier,
+    },
+    DistanceTraveled, GlobalParticleGravity, GroupLimits, ParticleQualitySettings,
+    ParticleSystemStat, ParticleSystemStats, ParticleTexture, ParticleWind,
 };
-use crate::{AnimatedIndex, AtlasIndex, Lerpable};
+use crate::{AnimatedIndex, AtlasIndex, DistanceAnimatedIndex};
 
 /// System label attached to the `SystemSet` provided in this plugin
 ///
 /// This is provided so that users can order their systems to run before/after this plugin.
+///
+/// The set is used in two schedules:
+/// - In `Update`, particles are aged, moved, recolored and cleaned up.
+/// - In `PostUpdate`, new particles are spawned after `TransformSystem::TransformPropagate`, so emitters
+///   spawn from their up-to-date `GlobalTransform` without lagging a frame behind moving parents.
 #[derive(Debug, SystemSet, Hash, Clone, PartialEq, Eq)]
 pub struct ParticleSystemSet;
 
+/// The shortest lifetime, in seconds, a particle can be spawned with.
+const MIN_PARTICLE_LIFETIME: f32 = 0.001;
+
+/// The lightest mass a particle can be spawned with, keeping forces from blowing up.
+const MIN_PARTICLE_MASS: f32 = 0.001;
+
+/// The percentage of its lifetime a particle has lived, treating a non-positive ``max_lifetime`` as already dead.
+fn lifetime_pct(lifetime: f32, max_lifetime: f32) -> f32 {
+    if max_lifetime > 0.0 {
+        lifetime / max_lifetime
+    } else {
+        1.0
+    }
+}
+
+/// The duration of the current frame in seconds, from [`bevy_time::Virtual`] or [`bevy_time::Real`] time, and
+/// clamped to ``max_delta_time`` when set.
+fn frame_delta(
+    use_scaled_time: bool,
+    max_delta_time: Option<f32>,
+    time: &Time,
+    raw_time: &Time<Real>,
+) -> f32 {
+    let delta_time = if use_scaled_time {
+        time.delta_seconds()
+    } else {
+        raw_time.delta_seconds()
+    };
+    max_delta_time.map_or(delta_time, |max_delta_time| delta_time.min(max_delta_time))
+}
+
+/// The longest frame, in seconds, the spawn rate catches up on.
+///
+/// This matches the default maximum delta of [`bevy_time::Virtual`], and keeps a long hitch or resuming from a
+/// pause from spawning a large batch of particles at once.
+const MAX_SPAWN_CATCH_UP_SECONDS: f32 = 0.25;
+
+/// How far behind its particle an outline sprite is drawn, in local units.
+const OUTLINE_Z_OFFSET: f32 = 0.0001;
+
+/// Whether particle queries are iterated on Bevy's compute task pool.
+const PARALLEL: bool = cfg!(not(any(target_arch = "wasm32", feature = "sequential")));
+
+/// Runs ``func`` on every item of ``query``, in parallel unless built for `wasm32` or with the `sequential` feature.
+fn for_each_mut<D, F, Func>(query: &mut Query<D, F>, func: Func)
+where
+    D: QueryData,
+    F: QueryFilter,
+    Func: Fn(QueryItem<'_, D>) + Send + Sync + Clone,
+{
+    for_each_mut_with(query, PARALLEL, func);
+}
+
+fn for_each_mut_with<D, F, Func>(query: &mut Query<D, F>, parallel: bool, func: Func)
+where
+    D: QueryData,
+    F: QueryFilter,
+    Func: Fn(QueryItem<'_, D>) + Send + Sync + Clone,
+{
+    if parallel {
+        query.par_iter_mut().for_each(func);
+    } else {
+        query.iter_mut().for_each(func);
+    }
+}
+
 #[allow(
     clippy::cast_sign_loss,
     clippy::cast_precision_loss,
@@ -35,18 +138,24 @@ pub fn particle_spawner(
         (
             Entity,
             &GlobalTransform,
-            &ParticleSystem,
+            Ref<ParticleSystem>,
             &mut ParticleCount,
             &mut RunningState,
             &mut BurstIndex,
+            Option<&ParentTint>,
+            Option<&DistanceLod>,
+            Option<&SystemVisibility>,
+            Option<&SpawnFilter>,
+            Has<DebrisMode>,
         ),
         With<Playing>,
     >,
     raw_time: Res<Time<Real>>,
     time: Res<Time>,
+    quality: Res<ParticleQualitySettings>,
+    mesh_samplers: Res<MeshSurfaceSamplers>,
     mut commands: Commands,
 ) {
-    let mut rng = rand::thread_rng();
     for (
         entity,
         global_transform,
@@ -54,318 +163,5425 @@ pub fn particle_spawner(
         mut particle_count,
         mut running_state,
         mut burst_index,
+        parent_tint,
+        distance_lod,
+        system_visibility,
+        spawn_filter,
+        debris,
     ) in &mut particle_systems
     {
-        if particle_system.use_scaled_time {
-            running_state.running_time += time.delta_seconds();
-        } else {
-            running_state.running_time += raw_time.delta_seconds();
+        let system_visibility = system_visibility.copied().unwrap_or_default();
+        if !system_visibility.simulate {
+            continue;
         }
+        let particle_system_changed = particle_system.is_changed();
+        let particle_system = particle_system.into_inner();
+        let previous_position = running_state
+            .previous_position
+            .replace(global_transform.translation());
 
-        if running_state.running_time.floor() > running_state.current_second + 0.5 {
-            running_state.current_second = running_state.running_time.floor();
-            running_state.spawned_this_second = 0;
+        if *particle_system.texture.image() == Handle::default() {
+            running_state.warn_once(SystemWarning::MissingTexture, || {
+                format!("Particle system {entity} has no texture set, its particles will be drawn as plain squares")
+            });
+        }
+        if particle_system.min_particles > particle_system.max_particles {
+            running_state.warn_once(SystemWarning::MinParticles, || {
+                format!("Particle system {entity} has more min_particles than max_particles, it keeps at most max_particles alive")
+            });
+        }
+        if particle_system
+            .axis_scale
+            .truncate()
+            .cmpeq(Vec2::ZERO)
+            .any()
+        {
+            running_state.warn_once(SystemWarning::ZeroAxisScale, || {
+                format!("Particle system {entity} has an axis_scale of zero on x or y, its particles will be invisible")
+            });
         }
 
-        if running_state.running_time >= particle_system.system_duration_seconds {
-            if particle_system.looping {
-                running_state.running_time -= particle_system.system_duration_seconds;
-                running_state.current_second = running_state.running_time.floor();
-                running_state.spawned_this_second = 0;
-                burst_index.0 = 0;
-            } else {
-                if particle_count.0 == 0 {
-                    if particle_system.despawn_on_finish {
-                        commands.entity(entity).despawn();
-                    } else {
-                        commands.entity(entity).remove::<Playing>();
-                    }
+        let delta_time = frame_delta(
+            particle_system.use_scaled_time,
+            particle_system.max_delta_time,
+            &time,
+            &raw_time,
+        );
+        running_state.running_time += delta_time;
+
+        if running_state.running_time >= particle_system.system_duration_seconds
+            && particle_system.loops_again(&running_state)
+        {
+            running_state.running_time -= particle_system.system_duration_seconds;
+            running_state.completed_loops += 1;
+            burst_index.0 = 0;
+            running_state.repeating_bursts_fired = 0;
+        }
+
+        if running_state.deferred_burst_particles > 0 {
+            running_state.deferred_burst_wait += delta_time;
+            if running_state.deferred_burst_wait > particle_system.deferred_burst_timeout {
+                running_state.deferred_burst_particles = 0;
+            }
+        }
+
+        // Once the emission has ended, only particles held back on previous frames are still spawned.
+        let emission_ended = particle_system.emission_ended(&running_state);
+        if emission_ended {
+            running_state.deferred_burst_particles = 0;
+        }
+        if particle_system.is_done_emitting(&running_state) {
+            if particle_system.is_finished(&running_state, &particle_count) {
+                if particle_system.despawn_on_finish {
+                    commands.entity(entity).despawn();
+                } else {
+                    commands.entity(entity).remove::<Playing>();
                 }
-                continue;
             }
+            continue;
         }
 
-        if particle_count.0 >= particle_system.max_particles {
+        let max_particles = ParticleQualitySettings::scale_count(
+            particle_system.max_particles,
+            quality.max_particles_scale,
+        );
+        if particle_count.0 >= max_particles {
             continue;
         }
 
+        let spawn_rate_scale =
+            quality.spawn_rate_scale * distance_lod.map_or(1.0, DistanceLod::rate_scale);
         let pct = running_state.running_time / particle_system.system_duration_seconds;
-        let remaining_particles = (particle_system.max_particles - particle_count.0) as f32;
-        let current_spawn_rate = particle_system.spawn_rate_per_second.at_lifetime_pct(pct);
-        let mut to_spawn = ((running_state.running_time - running_state.running_time.floor())
-            * current_spawn_rate
-            - running_state.spawned_this_second as f32)
-            .floor()
-            .clamp(0.0, remaining_particles) as usize;
-
-        let mut extra = 0;
-        if !particle_system.bursts.is_empty() {
-            if let Some(current_burst) = particle_system.bursts.get(burst_index.0) {
-                if running_state.running_time >= current_burst.time {
-                    extra += current_burst.count;
-                    burst_index.0 += 1;
+        let (particle_cap, current_spawn_rate) = match particle_system.emission_control {
+            EmissionControl::Rate => (
+                max_particles,
+                particle_system.spawn_rate_per_second.at_lifetime_pct(pct) * spawn_rate_scale,
+            ),
+            EmissionControl::SteadyState { target_alive } => {
+                let target = ParticleQualitySettings::scale_count(target_alive, spawn_rate_scale)
+                    .min(max_particles);
+                // With a constant spawn rate, the live population settles at `rate * average lifetime`.
+                let average_lifetime = particle_system.lifetime.average().max(f32::EPSILON);
+                (target, target as f32 / average_lifetime)
+            }
+        };
+        let remaining_particles = particle_cap.saturating_sub(particle_count.0) as f32;
+        let in_emission_window = particle_system
+            .emission_window
+            .as_ref()
+            .map_or(true, |window| window.contains(&running_state.running_time));
+        let mut to_spawn = 0;
+        if !emission_ended && current_spawn_rate > 0.0 && in_emission_window {
+            // Carry fractional particles between frames so low rates stay accurate.
+            running_state.spawn_accumulator +=
+                current_spawn_rate * delta_time.min(MAX_SPAWN_CATCH_UP_SECONDS);
+            to_spawn = running_state
+                .spawn_accumulator
+                .floor()
+                .clamp(0.0, remaining_particles) as usize;
+            // Particles held back by the cap are dropped rather than spawned all at once later.
+            running_state.spawn_accumulator =
+                (running_state.spawn_accumulator - to_spawn as f32).min(1.0);
+        }
+
+        let deferred = std::mem::take(&mut running_state.deferred_burst_particles);
+        let mut extra = deferred + std::mem::take(&mut running_state.pending_spawns);
+        if !emission_ended && !particle_system.bursts.is_empty() {
+            if particle_system_changed
+                || running_state.burst_order.len() != particle_system.bursts.len()
+            {
+                running_state.resolve_burst_order(particle_system);
+            }
+            // Fire every burst that is due, so bursts sharing a time or passed within a long frame
+            // aren't pushed back to later frames.
+            while let Some(&(time, count)) = running_state.burst_order.get(burst_index.0) {
+                if running_state.running_time < time {
+                    break;
                 }
+                extra += ParticleQualitySettings::scale_count(count, spawn_rate_scale);
+                burst_index.0 += 1;
             }
         }
-        if to_spawn == 0
-            && running_state.spawned_this_second == 0
-            && particle_count.0 < particle_system.max_particles
-            && current_spawn_rate > 0.0
+        if let Some(repeating_burst) = particle_system
+            .repeating_burst
+            .filter(|repeating_burst| !emission_ended && repeating_burst.interval > 0.0)
         {
-            to_spawn = 1;
+            let mut jitter_rng = None;
+            while running_state.repeating_bursts_fired as f32 * repeating_burst.interval
+                <= running_state.running_time
+            {
+                let rng = jitter_rng.get_or_insert_with(|| {
+                    repeating_burst_rng(particle_system.seed, &mut running_state)
+                });
+                extra += ParticleQualitySettings::scale_count(
+                    repeating_burst.get_count(rng),
+                    spawn_rate_scale,
+                );
+                running_state.repeating_bursts_fired += 1;
+            }
+        }
+        if !emission_ended && running_state.requested_particles > 0 {
+            extra += ParticleQualitySettings::scale_count(
+                running_state.requested_particles,
+                spawn_rate_scale,
+            );
+            running_state.requested_particles = 0;
+        }
+        if !emission_ended {
+            // Top up to the floor, counting particles already due this frame.
+            let floor = particle_system.min_particles.min(max_particles);
+            to_spawn += floor.saturating_sub(particle_count.0 + to_spawn + extra);
+        }
+        let capacity = max_particles.saturating_sub(particle_count.0 + to_spawn);
+        if extra > capacity {
+            if particle_system.deferred_bursts {
+                let remainder = extra - capacity;
+                if remainder > deferred {
+                    running_state.deferred_burst_wait = 0.0;
+                }
+                running_state.deferred_burst_particles = remainder;
+            }
+            extra = capacity;
+        }
+        if let Some(total) = particle_system.total_particles_to_emit {
+            let remaining = total.saturating_sub(running_state.total_spawned);
+            to_spawn = to_spawn.min(remaining);
+            extra = extra.min(remaining - to_spawn);
+        }
+        if let Some(max_spawns) = particle_system.max_spawns_per_frame {
+            // Hold back bursts before the spawn rate, so the steady emission keeps flowing.
+            let held_back = (to_spawn + extra).saturating_sub(max_spawns);
+            let held_back_extra = held_back.min(extra);
+            extra -= held_back_extra;
+            to_spawn -= held_back - held_back_extra;
+            running_state.pending_spawns = held_back;
         }
 
         if to_spawn == 0 && extra == 0 {
             continue;
         }
 
-        let origin_pos = match particle_system.space {
-            ParticleSpace::Local => Transform::default(),
-            ParticleSpace::World => Transform::from(*global_transform),
+        let mesh_sampler = match &particle_system.emitter_shape {
+            EmitterShape::MeshSurface(handle) => {
+                // No sampler exists until the mesh is loaded, or if it can't be sampled.
+                let Some(Some(sampler)) = mesh_samplers.0.get(&handle.id()) else {
+                    continue;
+                };
+                Some(sampler)
+            }
+            EmitterShape::CircleSegment(_) | EmitterShape::Line(_) => None,
+        };
+
+        let mut rng = seeded_rng(particle_system.seed, 0, &mut running_state);
+
+        let tint = parent_tint.map_or(Color::WHITE, |parent_tint| parent_tint.0);
+
+        let origin_pos = if particle_system.space.is_world() {
+            Transform::from(*global_transform)
+        } else {
+            Transform::default()
+        };
+        let elapsed_time = if particle_system.use_scaled_time {
+            time.elapsed_seconds_wrapped()
+        } else {
+            raw_time.elapsed_seconds_wrapped()
+        };
+        let spin = particle_system
+            .emitter_spin
+            .as_ref()
+            .map_or(Quat::IDENTITY, |spin| {
+                Quat::from_rotation_z(spin.at_lifetime_pct(pct))
+            });
+        let wander = particle_system
+            .emitter_wander
+            .as_ref()
+            .map_or(Vec3::ZERO, |noise| {
+                noise.sample(Vec2::ZERO, elapsed_time).extend(0.0)
+            });
+
+        // Spawns are spread back along the emitter's movement, ending at its current position.
+        let movement = match previous_position {
+            Some(previous_position)
+                if particle_system.space.is_world()
+                    && particle_system.interpolate_spawn_positions =>
+            {
+                global_transform.translation() - previous_position
+            }
+            _ => Vec3::ZERO,
         };
+        let frame_spawns = to_spawn + extra;
 
-        for _ in 0..to_spawn + extra {
-            let spawn_pos = particle_system.emitter_shape.sample(&mut rng);
+        let mut spawned = 0;
+        for index in 0..frame_spawns {
+            let spawn_pos = match &mesh_sampler {
+                Some(sampler) => sampler.sample(&mut rng),
+                None => particle_system.emitter_shape.sample(&mut rng),
+            };
 
-            let mut spawn_point = origin_pos.mul_transform(spawn_pos);
+            // The spin and wander are applied in the emitter's frame, so they turn and scale along with the
+            // emitter.
+            let mut pattern_pos = spawn_pos;
+            pattern_pos.rotate_around(Vec3::ZERO, spin);
+            pattern_pos.translation += wander;
+            let mut spawn_point = origin_pos.mul_transform(pattern_pos);
+            spawn_point.translation -=
+                movement * (frame_spawns - index - 1) as f32 / frame_spawns as f32;
 
-            let direction = spawn_point.rotation * Vec3::X;
+            if let Some(cell_size) = particle_system.spawn_grid_snap {
+                spawn_point.translation = snap_to_grid(
+                    spawn_point.translation,
+                    cell_size,
+                    particle_system.space,
+                    global_transform,
+                );
+            }
 
-            spawn_point.translation.z = particle_system
-                .z_value_override
+            let initial_velocity = particle_system
+                .initial_velocity
                 .as_ref()
-                .map_or(origin_pos.translation.z, |jittered_value| {
-                    jittered_value.get_value(&mut rng)
+                .map(|velocity| velocity.get_value(&mut rng));
+            let mut direction = match (initial_velocity, &particle_system.initial_velocity_override)
+            {
+                (Some(velocity), _) => velocity,
+                (None, Some(velocity)) => velocity.at_lifetime_pct(pct),
+                (None, None) => spawn_point.rotation * Vec3::X,
+            };
+            if let (None, Some(spread)) = (initial_velocity, particle_system.direction_spread) {
+                direction = spread_direction(direction, spread, &mut rng);
+            }
+
+            spawn_point.translation.z = match &particle_system.z_value_override {
+                Some(jittered_value) => jittered_value.get_value(&mut rng),
+                None => {
+                    origin_pos.translation.z + particle_system.z_offset_from_emitter.unwrap_or(0.0)
+                }
+            };
+
+            if let Some(spawn_filter) = spawn_filter {
+                let position = if particle_system.space.is_world() {
+                    spawn_point.translation
+                } else {
+                    global_transform.transform_point(spawn_point.translation)
+                };
+                if !(spawn_filter.0)(SpawnContext {
+                    emitter: entity,
+                    position,
+                }) {
+                    // The seeded stream still moves on next frame, so a vetoed position isn't sampled
+                    // again forever.
+                    continue;
+                }
+            }
+            spawned += 1;
+
+            let sampled_speed = particle_system.initial_speed.get_value(&mut rng);
+            let mut initial_speed = sampled_speed;
+            if let Some(curve) = &particle_system.speed_by_radius {
+                if let Some(distance) = particle_system
+                    .emitter_shape
+                    .normalized_distance(spawn_pos.translation)
+                {
+                    initial_speed *= curve.sample(distance);
+                }
+            }
+
+            let mut max_lifetime = particle_system.lifetime.get_value(&mut rng);
+            if let Some(curve) = &particle_system.lifetime_by_speed {
+                let max_speed = particle_system.initial_speed.max();
+                let normalized_speed = if max_speed > 0.0 {
+                    (sampled_speed / max_speed).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                max_lifetime *= curve.sample(normalized_speed);
+            }
+            if max_lifetime < MIN_PARTICLE_LIFETIME {
+                running_state.warn_once(SystemWarning::ShortLifetime, || {
+                    format!("Particle system {entity} sampled a lifetime of {max_lifetime}, particle lifetimes are clamped to at least {MIN_PARTICLE_LIFETIME}")
                 });
+                max_lifetime = MIN_PARTICLE_LIFETIME;
+            }
+            // Keep particles strictly younger than their lifetime so they aren't dead on arrival.
+            let initial_age = particle_system
+                .initial_age
+                .get_value(&mut rng)
+                .clamp(0.0, (max_lifetime - max_lifetime * f32::EPSILON).max(0.0));
+            if debris {
+                max_lifetime = f32::INFINITY;
+            }
+            let age_pct = lifetime_pct(initial_age, max_lifetime);
 
             let initial_scale = particle_system.initial_scale.get_value(&mut rng);
-            let particle_scale = initial_scale * particle_system.scale.at_lifetime_pct(0.0);
-            spawn_point.scale = Vec3::new(particle_scale, particle_scale, particle_scale);
+            let particle_scale = initial_scale * particle_system.scale.at_lifetime_pct(age_pct);
+            spawn_point.scale = particle_system.axis_scale * particle_scale;
 
-            if particle_system.rotate_to_movement_direction {
-                spawn_point.rotate_z(particle_system.initial_rotation.get_value(&mut rng));
-            } else {
+            let initial_rotation = particle_system.initial_rotation.get_value(&mut rng);
+            let mode_angle = particle_system
+                .initial_rotation_mode
+                .angle((spin * spawn_pos.translation).truncate());
+            if particle_system.align_to_velocity {
                 spawn_point.rotation =
-                    Quat::from_rotation_z(particle_system.initial_rotation.get_value(&mut rng));
+                    Quat::from_rotation_z(direction.y.atan2(direction.x) + initial_rotation);
+            } else if let Some(mode_angle) = mode_angle {
+                spawn_point.rotation =
+                    origin_pos.rotation * Quat::from_rotation_z(mode_angle + initial_rotation);
+            } else if particle_system.rotate_to_movement_direction {
+                spawn_point.rotate_z(initial_rotation);
+            } else {
+                spawn_point.rotation = Quat::from_rotation_z(initial_rotation);
+            }
+
+            let rotation_speed = particle_system.rotation_speed.get_value(&mut rng);
+            let seed = rng.gen();
+            let mass = particle_system
+                .mass
+                .get_value(&mut rng)
+                .max(MIN_PARTICLE_MASS);
+            let velocity =
+                Velocity::new(initial_velocity.unwrap_or(direction * initial_speed), true);
+            let color_context = ColorContext {
+                lifetime_pct: age_pct,
+                velocity: velocity.0,
+                position: spawn_point.translation,
+                seed,
+                system_pct: pct.clamp(0.0, 1.0),
             };
+            // Particles have only just spawned, so they haven't traveled any distance yet.
+            let color_pct = particle_system
+                .color_driver
+                .value(age_pct, velocity.0.length(), 0.0);
+            let color = particle_system.color_layers.iter().fold(
+                particle_system.color.evaluate_at(color_pct, &color_context),
+                |color, (op, layer)| op.blend(color, layer.evaluate_at(color_pct, &color_context)),
+            );
+            let color = match &particle_system.alpha_over_time {
+                Some(alpha_over_time) => alpha_over_time.apply(color, age_pct),
+                None => color,
+            };
+
+            let despawn_region = particle_system.despawn_region.filter(|region| {
+                particle_system.despawn_if_spawned_outside
+                    || region.contains(spawn_point.translation)
+            });
 
             let particle_bundle = ParticleBundle {
                 particle: Particle {
                     parent_system: entity,
-                    max_lifetime: particle_system.lifetime.get_value(&mut rng),
+                    max_lifetime,
                     max_distance: particle_system.max_distance,
+                    despawn_when_offscreen: particle_system.despawn_when_offscreen,
+                    despawn_region,
                     use_scaled_time: particle_system.use_scaled_time,
+                    max_delta_time: particle_system.max_delta_time,
+                    simulation_speed: particle_system.simulation_speed,
+                    simulation_speed_affects_lifetime: particle_system
+                        .simulation_speed_affects_lifetime,
                     initial_scale,
                     scale: particle_system.scale.clone(),
-                    rotation_speed: particle_system.rotation_speed.get_value(&mut rng),
+                    axis_scale: particle_system.axis_scale,
+                    rotation_speed,
+                    initial_rotation,
+                    align_to_velocity: particle_system.align_to_velocity,
+                    tint,
+                    color_driver: particle_system.color_driver,
+                    alpha_over_time: particle_system.alpha_over_time.clone(),
+                    flicker: particle_system.flicker,
+                    seed,
+                    system_pct: color_context.system_pct,
                     velocity_modifiers: particle_system.velocity_modifiers.clone(),
-                    despawn_with_parent: particle_system.despawn_particles_with_system,
+                    velocity_constraint: particle_system.velocity_constraint,
+                    mass,
+                    despawn_with_parent: particle_system.despawn_particles_with_system
+                        || matches!(particle_system.space, ParticleSpace::WorldAnchored),
                 },
-                velocity: Velocity::new(
-                    direction * particle_system.initial_speed.get_value(&mut rng),
-                    true,
-                ),
+                velocity,
                 distance: DistanceTraveled {
                     dist_squared: 0.0,
                     from: spawn_point.translation,
                 },
-                color: ParticleColor(particle_system.color.clone()),
-                ..ParticleBundle::default()
+                color: ParticleColor(
+                    particle_system.color.clone(),
+                    particle_system.color_layers.clone(),
+                ),
+                lifetime: Lifetime(initial_age),
             };
 
-            let texture: &Handle<Image> = match &particle_system.texture {
-                ParticleTexture::Sprite(image_handle) => image_handle,
-                ParticleTexture::TextureAtlas { texture, .. } => texture,
+            // Spawning runs after transform propagation, so fill in the global transform here to avoid
+            // drawing the particle at the origin for a frame.
+            let particle_global_transform = if particle_system.space.is_world() {
+                GlobalTransform::from(spawn_point)
+            } else {
+                global_transform.mul_transform(spawn_point)
             };
-
+            let sprite_color = apply_tint(color, tint);
             let sprite_bundle = SpriteBundle {
                 sprite: Sprite {
-                    color: particle_system.color.at_lifetime_pct(0.0),
+                    color: sprite_color,
                     custom_size: particle_system.rescale_texture,
                     ..Sprite::default()
                 },
-                texture: texture.clone(),
+                texture: particle_system.texture.image().clone(),
                 transform: spawn_point,
+                global_transform: particle_global_transform,
+                visibility: particle_visibility(system_visibility),
                 ..SpriteBundle::default()
             };
+            let animated_index = match &particle_system.texture {
+                ParticleTexture::TextureAtlas {
+                    index: AtlasIndex::Animated(animated_index),
+                    ..
+                } => Some(animated_index.for_particle(seed)),
+                _ => None,
+            };
+            let texture_atlas = match &particle_system.texture {
+                ParticleTexture::TextureAtlas { atlas, index, .. } => Some(TextureAtlas {
+                    layout: atlas.clone(),
+                    index: animated_index
+                        .as_ref()
+                        .map_or_else(|| index.get_value(&mut rng), AnimatedIndex::get_at_start),
+                }),
+                ParticleTexture::Sprite(_) => None,
+            };
+
+            let spawn_particle = |entity_commands: &mut EntityCommands| -> Entity {
+                entity_commands.insert(sprite_bundle);
+
+                if let Some(texture_atlas) = &texture_atlas {
+                    entity_commands.insert(texture_atlas.clone());
+                }
 
-            match particle_system.space {
-                ParticleSpace::Local => {
-                    commands.entity(entity).with_children(|parent| {
-                        let mut entity_commands = parent.spawn(particle_bundle);
+                if debris {
+                    entity_commands.insert(Debris);
+                }
 
-                        entity_commands.insert(sprite_bundle);
+                if let Some(animated_index) = &animated_index {
+                    entity_commands.insert(animated_index.clone());
+                }
+                if let ParticleTexture::TextureAtlas {
+                    index: AtlasIndex::DistanceAnimated(distance_index),
+                    ..
+                } = &particle_system.texture
+                {
+                    entity_commands.insert(distance_index.clone());
+                }
 
-                        if let ParticleTexture::TextureAtlas { atlas, index, .. } =
-                            &particle_system.texture
-                        {
-                            entity_commands.insert(TextureAtlas {
-                                layout: atlas.clone(),
-                                index: index.get_value(&mut rng),
-                            });
+                if !particle_system.lifetime_events.is_empty() {
+                    entity_commands.insert(LifetimeThresholds {
+                        thresholds: particle_system.lifetime_events.clone(),
+                        last_lifetime_pct: age_pct,
+                    });
+                }
 
-                            if let AtlasIndex::Animated(animated_index) = index {
-                                entity_commands.insert(animated_index.clone());
-                            };
-                        }
+                if let Some(outline) = particle_system.outline {
+                    entity_commands.with_children(|parent| {
+                        spawn_outline(
+                            parent,
+                            outline,
+                            particle_system,
+                            sprite_color.alpha(),
+                            texture_atlas.clone(),
+                            particle_global_transform,
+                        );
                     });
                 }
-                ParticleSpace::World => {
-                    let mut entity_commands = commands.spawn(particle_bundle);
-
-                    entity_commands.insert(sprite_bundle);
-
-                    if let ParticleTexture::TextureAtlas { atlas, index, .. } =
-                        &particle_system.texture
-                    {
-                        entity_commands.insert(TextureAtlas {
-                            layout: atlas.clone(),
-                            index: index.get_value(&mut rng),
-                        });
-
-                        if let AtlasIndex::Animated(animated_index) = index {
-                            entity_commands.insert(animated_index.clone());
-                        };
-                    }
+
+                entity_commands.id()
+            };
+
+            let particle_entity = if particle_system.space.is_world() {
+                spawn_particle(&mut commands.spawn(particle_bundle))
+            } else {
+                let mut particle_entity = Entity::PLACEHOLDER;
+                commands.entity(entity).with_children(|parent| {
+                    particle_entity = spawn_particle(&mut parent.spawn(particle_bundle));
+                });
+                particle_entity
+            };
+
+            if let Some(connect_particles) = &particle_system.connect_particles {
+                if let Some(previous) = running_state.last_spawned_particle {
+                    spawn_connection(
+                        &mut commands,
+                        connect_particles,
+                        previous,
+                        particle_entity,
+                        particle_global_transform,
+                    );
                 }
+                running_state.last_spawned_particle = Some(particle_entity);
             }
         }
-        // Don't count bursts in the normal spawn rate, but still count them in the particle cap.
-        running_state.spawned_this_second += to_spawn;
-        particle_count.0 += to_spawn + extra;
+        running_state.total_spawned += spawned;
+        particle_count.0 += spawned;
     }
 }
 
-pub(crate) fn particle_lifetime(
-    mut lifetime_query: Query<(&mut Lifetime, &Particle)>,
-    raw_time: Res<Time<Real>>,
-    time: Res<Time>,
+/// The [`MeshSurfaceSampler`] of each mesh used by an [`EmitterShape::MeshSurface`] emitter.
+///
+/// Meshes that can't be sampled map to `None`, so they are only checked once.
+#[derive(Debug, Default, Resource)]
+pub(crate) struct MeshSurfaceSamplers(HashMap<AssetId<Mesh>, Option<MeshSurfaceSampler>>);
+
+/// Builds the [`MeshSurfaceSampler`] of each mesh used by a playing system once it is loaded, rebuilding it when the
+/// mesh is modified.
+pub(crate) fn particle_mesh_samplers(
+    particle_systems: Query<&ParticleSystem, With<Playing>>,
+    meshes: Option<Res<Assets<Mesh>>>,
+    mesh_events: Option<Res<Events<AssetEvent<Mesh>>>>,
+    mut mesh_event_reader: Local<ManualEventReader<AssetEvent<Mesh>>>,
+    mut mesh_samplers: ResMut<MeshSurfaceSamplers>,
 ) {
-    lifetime_query
-        .par_iter_mut()
-        .for_each(|(mut lifetime, particle)| {
-            if particle.use_scaled_time {
-                lifetime.0 += time.delta_seconds();
-            } else {
-                lifetime.0 += raw_time.delta_seconds();
+    if let Some(mesh_events) = mesh_events {
+        for event in mesh_event_reader.read(&mesh_events) {
+            if let AssetEvent::Modified { id } | AssetEvent::Removed { id } = event {
+                mesh_samplers.0.remove(id);
             }
-        });
+        }
+    }
+
+    let Some(meshes) = meshes else {
+        return;
+    };
+    for particle_system in &particle_systems {
+        let EmitterShape::MeshSurface(handle) = &particle_system.emitter_shape else {
+            continue;
+        };
+        if mesh_samplers.0.contains_key(&handle.id()) {
+            continue;
+        }
+        let Some(mesh) = meshes.get(handle) else {
+            continue;
+        };
+        let sampler = MeshSurfaceSampler::new(mesh);
+        if sampler.is_none() {
+            warn_once!(
+                "EmitterShape::MeshSurface requires a triangle list mesh with positions and a non-zero area, no particles will be spawned"
+            );
+        }
+        mesh_samplers.0.insert(handle.id(), sampler);
+    }
 }
 
-pub(crate) fn particle_sprite_color(
-    mut particle_query: Query<(&Particle, &mut ParticleColor, &Lifetime, &mut Sprite)>,
+/// Picks a uniformly distributed direction within a cone of half-angle ``spread`` around ``direction``, keeping its
+/// length.
+fn spread_direction<R: Rng + ?Sized>(direction: Vec3, spread: f32, rng: &mut R) -> Vec3 {
+    if spread <= 0.0 || direction == Vec3::ZERO {
+        return direction;
+    }
+
+    // Sampling the cosine uniformly keeps the distribution even over the cap of the sphere.
+    let cos_theta: f32 = rng.gen_range(spread.min(PI).cos()..=1.0);
+    let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+    let azimuth: f32 = rng.gen_range(0.0..TAU);
+    let axis = direction.normalize();
+    let (u, v) = axis.any_orthonormal_pair();
+
+    (axis * cos_theta + (u * azimuth.cos() + v * azimuth.sin()) * sin_theta) * direction.length()
+}
+
+pub(crate) fn particle_emit_once_start(
+    mut triggered: Query<(Entity, &mut RunningState, &mut BurstIndex), Added<EmitOnce>>,
+    mut commands: Commands,
 ) {
-    particle_query.par_iter_mut().for_each(
-        |(particle, mut particle_color, lifetime, mut sprite)| {
-            let pct = lifetime.0 / particle.max_lifetime;
-            sprite.color = match &mut particle_color.0 {
-                ColorOverTime::Constant(color) => *color,
-                ColorOverTime::Lerp(lerp) => lerp.a.lerp(lerp.b, pct),
-                ColorOverTime::Gradient(curve) => curve.sample_mut(pct),
-            };
-        },
-    );
+    for (entity, mut running_state, mut burst_index) in &mut triggered {
+        running_state.restart();
+        burst_index.0 = 0;
+        commands.entity(entity).insert(Playing);
+    }
 }
 
-pub(crate) fn particle_texture_atlas_index(
-    mut particle_query: Query<(&Lifetime, &mut TextureAtlas, Option<&AnimatedIndex>)>,
+pub(crate) fn particle_emit_once_end(
+    triggered: Query<Entity, With<EmitOnce>>,
+    mut commands: Commands,
 ) {
-    particle_query
-        .par_iter_mut()
-        .for_each(|(lifetime, mut texture_atlas, anim_index)| {
-            if let Some(anim_index) = anim_index {
-                texture_atlas.index = anim_index.get_at_time(lifetime.0);
-            }
-        });
+    for entity in &triggered {
+        commands.entity(entity).remove::<(EmitOnce, Playing)>();
+    }
 }
 
-pub(crate) fn particle_transform(
-    mut particle_query: Query<(
-        &Particle,
-        &Lifetime,
-        &mut Velocity,
-        &mut DistanceTraveled,
-        &mut Transform,
+pub(crate) fn particle_seek(
+    mut seeking: Query<(
+        Entity,
+        &ParticleSystem,
+        &SeekTo,
+        &mut RunningState,
+        &mut BurstIndex,
+        &mut ParticleCount,
     )>,
+    particle_query: Query<(Entity, &Particle)>,
+    mut commands: Commands,
+) {
+    let mut rewound = EntityHashSet::default();
+    for (
+        entity,
+        particle_system,
+        seek_to,
+        mut running_state,
+        mut burst_index,
+        mut particle_count,
+    ) in &mut seeking
+    {
+        if seek_to.0 < running_state.running_time {
+            rewound.insert(entity);
+            running_state.last_spawned_particle = None;
+            particle_count.0 = 0;
+        }
+        particle_system.seek(&mut running_state, &mut burst_index, seek_to.0);
+        commands.entity(entity).remove::<SeekTo>();
+    }
+    if rewound.is_empty() {
+        return;
+    }
+    for (particle_entity, particle) in &particle_query {
+        if rewound.contains(&particle.parent_system) {
+            commands.entity(particle_entity).despawn_recursive();
+        }
+    }
+}
+
+#[allow(
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation
+)]
+pub(crate) fn particle_beat_emitter(
+    mut beat_query: Query<(&ParticleSystem, &mut BeatEmitter, &mut RunningState), With<Playing>>,
     raw_time: Res<Time<Real>>,
     time: Res<Time>,
 ) {
-    particle_query.par_iter_mut().for_each(
-        |(particle, lifetime, mut velocity, mut distance, mut transform)| {
-            let lifetime_pct = lifetime.0 / particle.max_lifetime;
+    for (particle_system, mut beat_emitter, mut running_state) in &mut beat_query {
+        if beat_emitter.interval <= 0.0 {
+            continue;
+        }
+        beat_emitter.next_beat_in -= frame_delta(
+            particle_system.use_scaled_time,
+            particle_system.max_delta_time,
+            &time,
+            &raw_time,
+        );
+        if beat_emitter.next_beat_in > 0.0 {
+            continue;
+        }
+        // Catch up on every beat that passed this frame at once, so a tiny interval doesn't loop over each of them.
+        // The scheduled beat keeps its time, so changes to the interval only affect the beats after it.
+        let beats = (-beat_emitter.next_beat_in / beat_emitter.interval).floor() as usize + 1;
+        running_state.requested_particles += beat_emitter.count.saturating_mul(beats);
+        beat_emitter.next_beat_in += beats as f32 * beat_emitter.interval;
+    }
+}
 
-            let (delta_time, elapsed_time) = if particle.use_scaled_time {
-                (time.delta_seconds(), time.elapsed_seconds_wrapped())
-            } else {
-                (raw_time.delta_seconds(), raw_time.elapsed_seconds_wrapped())
-            };
+pub(crate) fn particle_burst_requests(
+    mut burst_requests: EventReader<ParticleBurstRequest>,
+    mut running_states: Query<&mut RunningState, With<Playing>>,
+) {
+    for request in burst_requests.read() {
+        if let Ok(mut running_state) = running_states.get_mut(request.entity) {
+            running_state.requested_particles += request.count;
+        }
+    }
+}
 
-            // initialize precalculated values
-            let mut ppv = PrecalculatedParticleVariables::new();
+pub(crate) fn particle_system_tween(
+    mut tween_query: Query<(Entity, &mut ParticleSystem, &mut ParticleSystemTween)>,
+    raw_time: Res<Time<Real>>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (entity, mut particle_system, mut tween) in &mut tween_query {
+        if tween.start.is_none() {
+            tween.start = Some(Box::new(particle_system.clone()));
+        }
+        tween.elapsed += if particle_system.use_scaled_time {
+            time.delta_seconds()
+        } else {
+            raw_time.delta_seconds()
+        };
 
-            // Apply velocity modifiers to velocity
-            for modifier in &particle.velocity_modifiers {
-                use VelocityModifier::{Drag, Noise, Scalar, Vector};
-                match modifier {
-                    Vector(v) => {
-                        velocity.0 += v.at_lifetime_pct(lifetime_pct) * delta_time;
-                    }
+        let progress = tween.progress();
+        if progress >= 1.0 {
+            *particle_system = (*tween.target).clone();
+            commands.entity(entity).remove::<ParticleSystemTween>();
+        } else if let Some(start) = &tween.start {
+            // Only flag the system as changed when a value moved, so work keyed on `Changed<ParticleSystem>` doesn't
+            // rerun on every frame of a slow or flat tween.
+            if interpolate_particle_system(
+                particle_system.bypass_change_detection(),
+                start,
+                &tween.target,
+                progress,
+            ) {
+                particle_system.set_changed();
+            }
+        }
+    }
+}
 
-                    Scalar(v) => {
-                        let direction = ppv.get_particle_direction(&velocity.0);
-                        velocity.0 += v.at_lifetime_pct(lifetime_pct) * direction * delta_time;
-                    }
+/// Interpolates the values of ``particle_system`` that can be tweened, see [`ParticleSystemTween`].
+///
+/// Returns whether any value was changed.
+#[allow(
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation
+)]
+fn interpolate_particle_system(
+    particle_system: &mut ParticleSystem,
+    start: &ParticleSystem,
+    target: &ParticleSystem,
+    pct: f32,
+) -> bool {
+    let mut changed = false;
+    let max_particles = Lerpable::lerp(
+        &(start.max_particles as f32),
+        target.max_particles as f32,
+        pct,
+    );
+    changed |= replace_if_neq(
+        &mut particle_system.max_particles,
+        max_particles.round() as usize,
+    );
+    changed |= replace_if_neq(
+        &mut particle_system.system_duration_seconds,
+        Lerpable::lerp(
+            &start.system_duration_seconds,
+            target.system_duration_seconds,
+            pct,
+        ),
+    );
+    if let (Some(from), Some(to)) = (start.max_distance, target.max_distance) {
+        changed |= replace_if_neq(
+            &mut particle_system.max_distance,
+            Some(Lerpable::lerp(&from, to, pct)),
+        );
+    }
+    if let (Some(from), Some(to)) = (start.direction_spread, target.direction_spread) {
+        changed |= replace_if_neq(
+            &mut particle_system.direction_spread,
+            Some(Lerpable::lerp(&from, to, pct)),
+        );
+    }
 
-                    Drag(v) => {
-                        let current_drag = v.at_lifetime_pct(lifetime_pct);
-                        if current_drag > 0.0 {
-                            let drag_force =
-                                ppv.get_particle_sqr_speed(&velocity.0) * current_drag * delta_time;
-                            let direction = ppv.get_particle_direction(&velocity.0);
-                            velocity.0 -= direction * drag_force;
-                        }
-                    }
+    if let (ValueOverTime::Constant(from), ValueOverTime::Constant(to)) =
+        (&start.spawn_rate_per_second, &target.spawn_rate_per_second)
+    {
+        changed |= replace_constant(
+            &mut particle_system.spawn_rate_per_second,
+            from.lerp(*to, pct),
+        );
+    }
+    if let (ValueOverTime::Constant(from), ValueOverTime::Constant(to)) =
+        (&start.scale, &target.scale)
+    {
+        changed |= replace_constant(&mut particle_system.scale, from.lerp(*to, pct));
+    }
+    if let (ColorOverTime::Constant(from), ColorOverTime::Constant(to)) =
+        (&start.color, &target.color)
+    {
+        let color = from.lerp(*to, pct);
+        if !matches!(particle_system.color, ColorOverTime::Constant(current) if current == color) {
+            particle_system.color = ColorOverTime::Constant(color);
+            changed = true;
+        }
+    }
 
-                    Noise(n) => {
-                        let offset = n.sample(
-                            Vec2::new(transform.translation.x, transform.translation.y),
-                            elapsed_time,
-                        ) * delta_time;
-                        velocity.0 += Vec3::new(offset.x, offset.y, 0.0);
-                    }
-                }
-            }
-            transform.translation += velocity.0 * delta_time;
+    changed |= interpolate_jittered(
+        &mut particle_system.initial_speed,
+        &start.initial_speed,
+        &target.initial_speed,
+        pct,
+    );
+    changed |= interpolate_jittered(
+        &mut particle_system.lifetime,
+        &start.lifetime,
+        &target.lifetime,
+        pct,
+    );
+    changed |= interpolate_jittered(
+        &mut particle_system.initial_scale,
+        &start.initial_scale,
+        &target.initial_scale,
+        pct,
+    );
+    if let (
+        InitialRotation::Jittered(value),
+        InitialRotation::Jittered(start),
+        InitialRotation::Jittered(target),
+    ) = (
+        &mut particle_system.initial_rotation,
+        &start.initial_rotation,
+        &target.initial_rotation,
+    ) {
+        changed |= interpolate_jittered(value, start, target, pct);
+    }
+    changed |= interpolate_jittered(
+        &mut particle_system.rotation_speed,
+        &start.rotation_speed,
+        &target.rotation_speed,
+        pct,
+    );
+    changed
+}
 
-            transform.scale =
-                Vec3::splat(particle.initial_scale * particle.scale.at_lifetime_pct(lifetime_pct));
-            transform.rotate_z(particle.rotation_speed * time.delta_seconds());
+/// Interpolates a [`JitteredValue`] and its jitter range, returning whether either changed.
+fn interpolate_jittered(
+    value: &mut JitteredValue,
+    start: &JitteredValue,
+    target: &JitteredValue,
+    pct: f32,
+) -> bool {
+    let mut changed = replace_if_neq(&mut value.value, start.value.lerp(target.value, pct));
+    if let (Some(from), Some(to)) = (&start.jitter_range, &target.jitter_range) {
+        changed |= replace_if_neq(
+            &mut value.jitter_range,
+            Some(from.start.lerp(to.start, pct)..from.end.lerp(to.end, pct)),
+        );
+    }
+    changed
+}
 
-            distance.dist_squared = transform.translation.distance_squared(distance.from);
-        },
-    );
+/// Sets ``value`` to a constant, returning whether it was different.
+fn replace_constant(value: &mut ValueOverTime, constant: f32) -> bool {
+    if let ValueOverTime::Constant(current) = value {
+        return replace_if_neq(current, constant);
+    }
+    *value = ValueOverTime::Constant(constant);
+    true
 }
 
-pub(crate) fn particle_cleanup(
-    particle_query: Query<(Entity, &Particle, &Lifetime, &DistanceTraveled)>,
+/// Sets ``value`` to ``new_value``, returning whether it was different.
+fn replace_if_neq<T: PartialEq>(value: &mut T, new_value: T) -> bool {
+    if *value == new_value {
+        false
+    } else {
+        *value = new_value;
+        true
+    }
+}
+
+pub(crate) fn particle_distance_lod(
+    mut lod_query: Query<(&mut DistanceLod, &GlobalTransform), With<Playing>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+) {
+    for (mut lod, global_transform) in &mut lod_query {
+        let emitter = global_transform.translation();
+        let nearest = camera_query
+            .iter()
+            .filter(|(camera, _)| camera.is_active)
+            .map(|(_, camera_transform)| camera_transform.translation().distance(emitter))
+            .min_by(f32::total_cmp);
+        lod.rate_scale = nearest.map_or(1.0, |distance| lod.rate_scale_at(distance));
+    }
+}
+
+pub(crate) fn particle_count_reconciliation(
+    particle_query: Query<&Particle>,
+    mut particle_count_query: Query<(Entity, &mut ParticleCount)>,
+) {
+    let mut live_counts = EntityHashMap::<usize>::default();
+    for particle in &particle_query {
+        *live_counts.entry(particle.parent_system).or_default() += 1;
+    }
+
+    for (entity, mut particle_count) in &mut particle_count_query {
+        let live_count = live_counts.get(&entity).copied().unwrap_or_default();
+        if particle_count.0 != live_count {
+            particle_count.0 = live_count;
+        }
+    }
+}
+
+pub(crate) fn particle_system_stats(
+    particle_systems: Query<(
+        Entity,
+        &ParticleSystem,
+        &ParticleCount,
+        &RunningState,
+        Has<Playing>,
+    )>,
+    mut stats: ResMut<ParticleSystemStats>,
+) {
+    stats.systems.clear();
+    stats.systems.extend(particle_systems.iter().map(
+        |(entity, particle_system, particle_count, running_state, playing)| ParticleSystemStat {
+            entity,
+            count: particle_count.0,
+            playing,
+            looping: particle_system.looping,
+            progress: particle_system.progress(running_state),
+        },
+    ));
+}
+
+pub(crate) fn particle_group_limits(
+    changed_systems: Query<(Entity, &ParticleSystemGroup), Changed<ParticleSystemGroup>>,
+    groups: Query<&ParticleSystemGroup>,
+    group_limits: Res<GroupLimits>,
+    mut members: Local<HashMap<GroupId, VecDeque<Entity>>>,
+    mut commands: Commands,
+) {
+    // Systems are tracked in the order they joined their group, oldest first. A system moved to another group
+    // joins the back of its new group, and is dropped from its old one below.
+    for (entity, group) in &changed_systems {
+        let entities = members.entry(group.0).or_default();
+        if !entities.contains(&entity) {
+            entities.push_back(entity);
+        }
+    }
+    for (group, entities) in &mut *members {
+        entities.retain(|entity| groups.get(*entity).is_ok_and(|current| current.0 == *group));
+        let Some(&limit) = group_limits.limits.get(group) else {
+            continue;
+        };
+        while entities.len() > limit {
+            if let Some(oldest) = entities.pop_front() {
+                commands.entity(oldest).despawn_recursive();
+            }
+        }
+    }
+    members.retain(|_, entities| !entities.is_empty());
+}
+
+pub(crate) fn particle_lifetime_events(
+    mut particle_query: Query<(Entity, &Particle, &Lifetime, &mut LifetimeThresholds)>,
+    mut threshold_events: EventWriter<ParticleLifetimeThreshold>,
+) {
+    for (entity, particle, lifetime, mut lifetime_thresholds) in &mut particle_query {
+        let pct = lifetime_pct(lifetime.0, particle.max_lifetime);
+        let last_pct = lifetime_thresholds.last_lifetime_pct;
+        if pct <= last_pct {
+            continue;
+        }
+        // Send every threshold crossed since the last check, as a long frame can cross several at once.
+        let mut crossed: Vec<f32> = lifetime_thresholds
+            .thresholds
+            .iter()
+            .copied()
+            .filter(|threshold| last_pct < *threshold && *threshold <= pct)
+            .collect();
+        crossed.sort_by(f32::total_cmp);
+        threshold_events.send_batch(
+            crossed
+                .into_iter()
+                .map(|threshold| ParticleLifetimeThreshold { entity, threshold }),
+        );
+        lifetime_thresholds.last_lifetime_pct = pct;
+    }
+}
+
+/// The [`SystemVisibility`] of the system that spawned a particle, defaulting to fully visible and simulated.
+fn system_visibility_of(
+    system_visibilities: &Query<&SystemVisibility>,
+    particle: &Particle,
+) -> SystemVisibility {
+    system_visibilities
+        .get(particle.parent_system)
+        .copied()
+        .unwrap_or_default()
+}
+
+fn particle_visibility(system_visibility: SystemVisibility) -> Visibility {
+    if system_visibility.render {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    }
+}
+
+pub(crate) fn particle_lifetime(
+    mut lifetime_query: Query<(&mut Lifetime, &Particle)>,
+    system_visibilities: Query<&SystemVisibility>,
+    raw_time: Res<Time<Real>>,
+    time: Res<Time>,
+) {
+    for_each_mut(&mut lifetime_query, |(mut lifetime, particle)| {
+        if !system_visibility_of(&system_visibilities, particle).simulate {
+            return;
+        }
+        let delta_time = frame_delta(
+            particle.use_scaled_time,
+            particle.max_delta_time,
+            &time,
+            &raw_time,
+        );
+        if particle.simulation_speed_affects_lifetime {
+            lifetime.0 += delta_time * particle.simulation_speed;
+        } else {
+            lifetime.0 += delta_time;
+        }
+    });
+}
+
+/// Rounds the x and y of ``translation`` to the nearest multiple of ``cell_size`` in world space.
+///
+/// ``translation`` is relative to the emitter when using [`ParticleSpace::Local`].
+fn snap_to_grid(
+    translation: Vec3,
+    cell_size: f32,
+    space: ParticleSpace,
+    global_transform: &GlobalTransform,
+) -> Vec3 {
+    if cell_size <= 0.0 {
+        return translation;
+    }
+
+    let snap = |world: Vec3| {
+        Vec3::new(
+            (world.x / cell_size).round() * cell_size,
+            (world.y / cell_size).round() * cell_size,
+            world.z,
+        )
+    };
+
+    if space.is_world() {
+        return snap(translation);
+    }
+    let world = global_transform.transform_point(translation);
+    global_transform
+        .affine()
+        .inverse()
+        .transform_point3(snap(world))
+}
+
+/// The next generator of a system, continuing its seeded stream from [`RunningState::seeded_draws`].
+///
+/// ``rotation`` is applied to the seed, so generators used for different purposes don't share values.
+fn seeded_rng(seed: Option<u64>, rotation: u32, running_state: &mut RunningState) -> StdRng {
+    match seed {
+        Some(seed) => {
+            let draw = running_state.seeded_draws;
+            running_state.seeded_draws += 1;
+            StdRng::seed_from_u64(
+                seed.rotate_left(rotation) ^ draw.wrapping_mul(0x9E37_79B9_7F4A_7C15),
+            )
+        }
+        None => StdRng::from_rng(rand::thread_rng())
+            .expect("thread_rng should never fail to seed another generator"),
+    }
+}
+
+/// The generator used to jitter the counts of a [`RepeatingBurst`](crate::RepeatingBurst).
+///
+/// This is kept apart from the spawning generator, so jittered counts don't change which particles a seeded system
+/// spawns.
+fn repeating_burst_rng(seed: Option<u64>, running_state: &mut RunningState) -> StdRng {
+    seeded_rng(seed, 32, running_state)
+}
+
+/// Spawns the outline sprite of a particle, see [`ParticleSystem::outline`].
+fn spawn_outline(
+    parent: &mut ChildBuilder,
+    outline: ParticleOutline,
+    particle_system: &ParticleSystem,
+    particle_alpha: f32,
+    texture_atlas: Option<TextureAtlas>,
+    particle_global_transform: GlobalTransform,
+) {
+    // Push the outline just behind the particle, without reaching the particles drawn behind it.
+    let transform = Transform::from_xyz(0.0, 0.0, -OUTLINE_Z_OFFSET)
+        .with_scale(Vec3::splat(1.0 + outline.thickness));
+    let mut entity_commands = parent.spawn((
+        ParticleOutlineSprite {
+            color: outline.color,
+        },
+        SpriteBundle {
+            sprite: Sprite {
+                color: outline
+                    .color
+                    .with_alpha(outline.color.alpha() * particle_alpha),
+                custom_size: particle_system.rescale_texture,
+                ..Sprite::default()
+            },
+            texture: particle_system.texture.image().clone(),
+            transform,
+            global_transform: particle_global_transform.mul_transform(transform),
+            ..SpriteBundle::default()
+        },
+    ));
+
+    if let Some(texture_atlas) = texture_atlas {
+        entity_commands.insert(texture_atlas);
+    }
+}
+
+/// Spawns the segment connecting ``from`` to the newly spawned ``to``, see [`ParticleSystem::connect_particles`].
+///
+/// The segment starts collapsed onto ``to``, and is stretched to ``from`` by [`particle_connections`].
+fn spawn_connection(
+    commands: &mut Commands,
+    connect_particles: &ConnectParticles,
+    from: Entity,
+    to: Entity,
+    to_global_transform: GlobalTransform,
+) {
+    let transform = Transform::from_translation(to_global_transform.translation());
+    commands.spawn((
+        ParticleConnection {
+            from,
+            to,
+            color: connect_particles.color.clone(),
+            width: connect_particles.width,
+        },
+        SpriteBundle {
+            sprite: Sprite {
+                custom_size: Some(Vec2::new(0.0, connect_particles.width)),
+                ..Sprite::default()
+            },
+            transform,
+            global_transform: transform.into(),
+            ..SpriteBundle::default()
+        },
+    ));
+}
+
+/// Multiplies ``color`` by ``tint`` in linear space.
+fn apply_tint(color: Color, tint: Color) -> Color {
+    if tint == Color::WHITE {
+        return color;
+    }
+
+    let color = color.to_linear();
+    let tint = tint.to_linear();
+    LinearRgba::new(
+        color.red * tint.red,
+        color.green * tint.green,
+        color.blue * tint.blue,
+        color.alpha * tint.alpha,
+    )
+    .into()
+}
+
+/// Evaluates ``color_over_time``, letting gradients remember the last sampled point.
+fn sample_color(color_over_time: &mut ColorOverTime, pct: f32, context: &ColorContext) -> Color {
+    match color_over_time {
+        ColorOverTime::Gradient(curve) => curve.sample_mut(pct),
+        ColorOverTime::AnimatedLerp { a, b } => a
+            .sample_mut(context.system_pct)
+            .lerp(b.sample_mut(context.system_pct), pct),
+        color_over_time => color_over_time.evaluate_at(pct, context),
+    }
+}
+
+pub(crate) fn particle_sprite_color(
+    mut particle_query: Query<(
+        &Particle,
+        &mut ParticleColor,
+        &Lifetime,
+        &Velocity,
+        &Transform,
+        Option<&DistanceTraveled>,
+        &mut Sprite,
+        &mut Visibility,
+    )>,
+    system_visibilities: Query<&SystemVisibility>,
+) {
+    for_each_mut(
+        &mut particle_query,
+        |(
+            particle,
+            mut particle_color,
+            lifetime,
+            velocity,
+            transform,
+            distance,
+            mut sprite,
+            mut visibility,
+        )| {
+            let system_visibility = system_visibility_of(&system_visibilities, particle);
+            visibility.set_if_neq(particle_visibility(system_visibility));
+            if !system_visibility.render {
+                return;
+            }
+
+            let context = ColorContext {
+                lifetime_pct: lifetime_pct(lifetime.0, particle.max_lifetime),
+                velocity: velocity.0,
+                position: transform.translation,
+                seed: particle.seed,
+                system_pct: particle.system_pct,
+            };
+            let color_pct = particle.color_driver.value(
+                context.lifetime_pct,
+                context.speed(),
+                distance.map_or(0.0, |distance| distance.dist_squared.sqrt()),
+            );
+            let ParticleColor(base, layers) = &mut *particle_color;
+            let color = layers.iter_mut().fold(
+                sample_color(base, color_pct, &context),
+                |color, (op, layer)| op.blend(color, sample_color(layer, color_pct, &context)),
+            );
+            let color = match &particle.alpha_over_time {
+                Some(alpha_over_time) => alpha_over_time.apply(color, context.lifetime_pct),
+                None => color,
+            };
+            let color = apply_tint(color, particle.tint);
+            sprite.color = match particle.flicker {
+                Some(flicker) => {
+                    color.with_alpha(color.alpha() * flicker.sample(particle.seed, lifetime.0))
+                }
+                None => color,
+            };
+        },
+    );
+}
+
+pub(crate) fn particle_texture_atlas_index(
+    mut particle_query: Query<(
+        Option<&Particle>,
+        &Lifetime,
+        &DistanceTraveled,
+        &mut TextureAtlas,
+        Option<&AnimatedIndex>,
+        Option<&DistanceAnimatedIndex>,
+    )>,
+    system_visibilities: Query<&SystemVisibility>,
+) {
+    for_each_mut(
+        &mut particle_query,
+        |(particle, lifetime, distance, mut texture_atlas, anim_index, distance_index)| {
+            if particle.is_some_and(|particle| {
+                !system_visibility_of(&system_visibilities, particle).render
+            }) {
+                return;
+            }
+            if let Some(anim_index) = anim_index {
+                texture_atlas.index = anim_index.get_at_time(lifetime.0);
+            } else if let Some(distance_index) = distance_index {
+                texture_atlas.index = distance_index.get_at_distance(distance.dist_squared.sqrt());
+            }
+        },
+    );
+}
+
+pub(crate) fn particle_density_alpha(
+    density_systems: Query<(Entity, &DensityAlpha)>,
+    mut particle_query: Query<(&Particle, &Transform, &mut Sprite)>,
+    system_visibilities: Query<&SystemVisibility>,
+) {
+    let density_alphas: EntityHashMap<DensityAlpha> = density_systems
+        .iter()
+        .filter(|(_, density_alpha)| density_alpha.cell_size > 0.0)
+        .map(|(entity, density_alpha)| (entity, *density_alpha))
+        .collect();
+    if density_alphas.is_empty() {
+        return;
+    }
+
+    let cell_of = |particle: &Particle, transform: &Transform| {
+        let density_alpha = density_alphas.get(&particle.parent_system)?;
+        if !system_visibility_of(&system_visibilities, particle).render {
+            return None;
+        }
+        let cell = (transform.translation.truncate() / density_alpha.cell_size)
+            .floor()
+            .as_ivec2();
+        Some((particle.parent_system, cell))
+    };
+    let mut counts: HashMap<(Entity, IVec2), usize> = HashMap::new();
+    for (particle, transform, _) in &particle_query {
+        if let Some(cell) = cell_of(particle, transform) {
+            *counts.entry(cell).or_default() += 1;
+        }
+    }
+    // The sprite colors were just recomputed from scratch, so the fade doesn't compound between frames.
+    for (particle, transform, mut sprite) in &mut particle_query {
+        let Some(cell) = cell_of(particle, transform) else {
+            continue;
+        };
+        let alpha_scale = density_alphas[&particle.parent_system].alpha_scale(counts[&cell]);
+        if alpha_scale < 1.0 {
+            let alpha = sprite.color.alpha();
+            sprite.color.set_alpha(alpha * alpha_scale);
+        }
+    }
+}
+
+pub(crate) fn particle_outline(
+    mut outline_query: Query<
+        (
+            &Parent,
+            &ParticleOutlineSprite,
+            &mut Sprite,
+            Option<&mut TextureAtlas>,
+        ),
+        Without<Particle>,
+    >,
+    particle_query: Query<(&Sprite, Option<&TextureAtlas>), With<Particle>>,
+) {
+    for_each_mut(
+        &mut outline_query,
+        |(parent, outline, mut sprite, texture_atlas)| {
+            let Ok((particle_sprite, particle_atlas)) = particle_query.get(parent.get()) else {
+                return;
+            };
+
+            sprite.color = outline
+                .color
+                .with_alpha(outline.color.alpha() * particle_sprite.color.alpha());
+            if let (Some(mut texture_atlas), Some(particle_atlas)) = (texture_atlas, particle_atlas)
+            {
+                texture_atlas.index = particle_atlas.index;
+            }
+        },
+    );
+}
+
+/// Stretches each [`ParticleConnection`] between its two particles, despawning it once either is gone.
+///
+/// This runs after transform propagation, so segments line up with where the particles are drawn this frame.
+pub(crate) fn particle_connections(
+    mut connection_query: Query<(
+        Entity,
+        &ParticleConnection,
+        &mut Sprite,
+        &mut Transform,
+        &mut GlobalTransform,
+        &mut Visibility,
+    )>,
+    particle_query: Query<
+        (
+            &Particle,
+            &Lifetime,
+            &Velocity,
+            &Transform,
+            &GlobalTransform,
+            &Visibility,
+        ),
+        Without<ParticleConnection>,
+    >,
+    mut commands: Commands,
+) {
+    for (entity, connection, mut sprite, mut transform, mut global_transform, mut visibility) in
+        &mut connection_query
+    {
+        let (Ok(from), Ok((_, _, _, _, to_global_transform, _))) = (
+            particle_query.get(connection.from),
+            particle_query.get(connection.to),
+        ) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        let (particle, lifetime, velocity, from_transform, from_global_transform, from_visibility) =
+            from;
+
+        let start = from_global_transform.translation();
+        let end = to_global_transform.translation();
+        let delta = end - start;
+        // Draw the segment just behind both of its particles.
+        transform.translation = ((start + end) / 2.0).with_z(start.z.min(end.z) - OUTLINE_Z_OFFSET);
+        transform.rotation = Quat::from_rotation_z(delta.y.atan2(delta.x));
+        *global_transform = (*transform).into();
+        sprite.custom_size = Some(Vec2::new(delta.truncate().length(), connection.width));
+
+        let color = connection.color.evaluate(&ColorContext {
+            lifetime_pct: lifetime_pct(lifetime.0, particle.max_lifetime),
+            velocity: velocity.0,
+            position: from_transform.translation,
+            seed: particle.seed,
+            system_pct: particle.system_pct,
+        });
+        sprite.color = apply_tint(color, particle.tint);
+        visibility.set_if_neq(*from_visibility);
+    }
+}
+
+pub(crate) fn particle_collect_positions(
+    mut commands: Commands,
+    missing_positions: Query<Entity, (With<CollectParticlePositions>, Without<ParticlePositions>)>,
+    mut positions_query: Query<&mut ParticlePositions, With<CollectParticlePositions>>,
+    particle_query: Query<(&Particle, &GlobalTransform)>,
+) {
+    for entity in &missing_positions {
+        commands.entity(entity).insert(ParticlePositions::default());
+    }
+
+    for mut positions in &mut positions_query {
+        positions.0.clear();
+    }
+    for (particle, global_transform) in &particle_query {
+        if let Ok(mut positions) = positions_query.get_mut(particle.parent_system) {
+            positions.0.push(global_transform.translation());
+        }
+    }
+}
+
+pub(crate) fn particle_transform(
+    mut particle_query: Query<(
+        &Particle,
+        &Lifetime,
+        &mut Velocity,
+        &mut DistanceTraveled,
+        &mut Transform,
+    )>,
+    particle_systems: Query<(
+        &ParticleSystem,
+        Option<&SystemVisibility>,
+        Option<&AffectedByWind>,
+    )>,
+    raw_time: Res<Time<Real>>,
+    time: Res<Time>,
+    gravity: Res<GlobalParticleGravity>,
+    wind: Res<ParticleWind>,
+) {
+    for_each_mut(
+        &mut particle_query,
+        |(particle, lifetime, mut velocity, mut distance, mut transform)| {
+            let parent_system = particle_systems.get(particle.parent_system).ok();
+            if parent_system
+                .and_then(|(_, system_visibility, _)| system_visibility)
+                .is_some_and(|system_visibility| !system_visibility.simulate)
+            {
+                return;
+            }
+
+            // Follow the live system's modifiers so runtime edits apply to existing particles,
+            // and fall back to the spawned copy once the system is gone.
+            let (velocity_modifiers, velocity_constraint) = parent_system.map_or(
+                (&particle.velocity_modifiers, particle.velocity_constraint),
+                |(particle_system, _, _)| {
+                    (
+                        &particle_system.velocity_modifiers,
+                        particle_system.velocity_constraint,
+                    )
+                },
+            );
+
+            let lifetime_pct = lifetime_pct(lifetime.0, particle.max_lifetime);
+
+            let elapsed_time = if particle.use_scaled_time {
+                time.elapsed_seconds_wrapped()
+            } else {
+                raw_time.elapsed_seconds_wrapped()
+            };
+            let delta_time = frame_delta(
+                particle.use_scaled_time,
+                particle.max_delta_time,
+                &time,
+                &raw_time,
+            ) * particle.simulation_speed;
+
+            // initialize precalculated values
+            let mut ppv = PrecalculatedParticleVariables::new();
+
+            // Only forces and drag depend on mass, the other modifiers accelerate every particle alike.
+            let force_delta_time = delta_time / particle.mass;
+
+            // Apply velocity modifiers to velocity
+            for modifier in velocity_modifiers {
+                use VelocityModifier::{Drag, GlobalGravity, Noise, Orbit, Scalar, Vector};
+                match modifier {
+                    Vector(v) => {
+                        velocity.0 += v.at_lifetime_pct(lifetime_pct) * force_delta_time;
+                    }
+
+                    Scalar(v) => {
+                        let direction = ppv.get_particle_direction(&velocity.0);
+                        velocity.0 += v.at_lifetime_pct(lifetime_pct) * direction * delta_time;
+                    }
+
+                    Drag(v) => {
+                        let current_drag = v.at_lifetime_pct(lifetime_pct);
+                        if current_drag > 0.0 {
+                            let drag_force = ppv.get_particle_sqr_speed(&velocity.0)
+                                * current_drag
+                                * force_delta_time;
+                            let direction = ppv.get_particle_direction(&velocity.0);
+                            // Drag can stop a particle, but never push it backwards.
+                            let speed = ppv.get_particle_speed(&velocity.0);
+                            velocity.0 -= direction * drag_force.min(speed);
+                        }
+                    }
+
+                    Noise(n) => {
+                        let offset = n.sample(
+                            Vec2::new(transform.translation.x, transform.translation.y),
+                            elapsed_time,
+                        ) * delta_time;
+                        velocity.0 += Vec3::new(offset.x, offset.y, 0.0);
+                    }
+
+                    GlobalGravity(multiplier) => {
+                        velocity.0 += gravity.0 * *multiplier * delta_time;
+                    }
+
+                    Orbit {
+                        center,
+                        strength,
+                        axis,
+                    } => {
+                        // The tangent is undefined at the center itself, so no force is applied there.
+                        let tangent = (*center - transform.translation)
+                            .cross(*axis)
+                            .normalize_or_zero();
+                        velocity.0 += tangent * strength.at_lifetime_pct(lifetime_pct) * delta_time;
+                    }
+                }
+            }
+            if let Some(constraint) = velocity_constraint {
+                velocity.0 = constraint.apply(velocity.0);
+            }
+            transform.translation += velocity.0 * delta_time;
+            if let Some((_, _, Some(affected_by_wind))) = parent_system {
+                let wind_velocity = wind.velocity_at(transform.translation, elapsed_time);
+                transform.translation += wind_velocity * affected_by_wind.0 * delta_time;
+            }
+
+            transform.scale = particle.axis_scale
+                * (particle.initial_scale * particle.scale.at_lifetime_pct(lifetime_pct));
+            if particle.align_to_velocity && velocity.0.truncate() != Vec2::ZERO {
+                // Compose the motion frame with the spin, so spinning doesn't fight the alignment.
+                let spin = particle.initial_rotation + particle.rotation_speed * lifetime.0;
+                transform.rotation = Quat::from_rotation_z(velocity.0.y.atan2(velocity.0.x))
+                    * Quat::from_rotation_z(spin);
+            } else {
+                transform.rotate_z(particle.rotation_speed * delta_time);
+            }
+
+            distance.dist_squared = transform.translation.distance_squared(distance.from);
+        },
+    );
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) fn particle_cleanup(
+    particle_query: Query<(
+        Entity,
+        &Particle,
+        &Lifetime,
+        &DistanceTraveled,
+        &Transform,
+        &GlobalTransform,
+    )>,
+    camera_query: Query<(&Camera, &Frustum)>,
     mut particle_count_query: Query<&mut ParticleCount>,
     mut commands: Commands,
 ) {
-    for (entity, particle, lifetime, distance) in particle_query.iter() {
+    for (entity, particle, lifetime, distance, transform, global_transform) in particle_query.iter()
+    {
+        // A particle that is both expired and offscreen must only be counted down once.
         if lifetime.0 >= particle.max_lifetime
             || (particle.max_distance.is_some()
                 && distance.dist_squared >= particle.max_distance.unwrap().powi(2))
+            || particle
+                .despawn_region
+                .is_some_and(|region| !region.contains(transform.translation))
+            || is_offscreen(particle, lifetime, global_transform, &camera_query)
         {
             if let Ok(mut particle_count) = particle_count_query.get_mut(particle.parent_system) {
                 if particle_count.0 > 0 {
                     particle_count.0 -= 1;
                 }
             }
-            commands.entity(entity).despawn();
+            commands.entity(entity).despawn_recursive();
         } else if particle.despawn_with_parent
             && commands.get_entity(particle.parent_system).is_none()
         {
-            commands.entity(entity).despawn();
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Whether a particle with an [`OffscreenDespawn`](crate::OffscreenDespawn) has left the view of every active camera
+/// once its grace period is over.
+///
+/// With no active camera there is no view to leave, so particles are treated as visible rather than all culled.
+fn is_offscreen(
+    particle: &Particle,
+    lifetime: &Lifetime,
+    global_transform: &GlobalTransform,
+    camera_query: &Query<(&Camera, &Frustum)>,
+) -> bool {
+    let Some(offscreen) = particle.despawn_when_offscreen else {
+        return false;
+    };
+
+    if lifetime.0 < offscreen.grace_period {
+        return false;
+    }
+
+    let bounds = Sphere {
+        center: global_transform.translation().into(),
+        radius: offscreen.margin,
+    };
+    let mut cameras = camera_query
+        .iter()
+        .filter(|(camera, _)| camera.is_active)
+        .peekable();
+    if cameras.peek().is_none() {
+        return false;
+    }
+    !cameras.any(|(_, frustum)| frustum.intersects_sphere(&bounds, false))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        any::TypeId,
+        f32::consts::{FRAC_PI_2, PI, TAU},
+        ops::Range,
+        sync::Mutex,
+        time::Duration,
+    };
+
+    use approx::assert_relative_eq;
+    use bevy_app::{App, Update};
+    use bevy_asset::{AssetEvent, Assets, Handle};
+    use bevy_color::{Alpha, Color, ColorToComponents, LinearRgba};
+    use bevy_ecs::{
+        entity::EntityHashMap,
+        prelude::{AppTypeRegistry, Component, Entity, Events, Mut, Query, With, World},
+        system::RunSystemOnce,
+    };
+    use bevy_hierarchy::{BuildWorldChildren, DespawnRecursiveExt, Parent};
+    use bevy_math::{Mat4, Quat, Vec2, Vec3};
+    use bevy_reflect::{std_traits::ReflectDefault, FromReflect, Reflect};
+    use bevy_render::{
+        camera::Camera,
+        mesh::{Mesh, PrimitiveTopology},
+        primitives::Frustum,
+        render_asset::RenderAssetUsages,
+        texture::Image,
+        view::Visibility,
+    };
+    use bevy_sprite::prelude::{Sprite, TextureAtlas};
+    use bevy_time::{Real, Time};
+    use bevy_transform::{
+        prelude::{GlobalTransform, Transform, TransformBundle},
+        TransformPlugin,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::{for_each_mut_with, spread_direction};
+    use crate::components::SystemWarning;
+    use crate::{
+        AffectedByWind, AlphaOverTime, AnimatedIndex, AtlasIndex, BeatEmitter, BurstIndex,
+        CircleSegment, CollectParticlePositions, ColorBlendOp, ColorDriver, ColorOverTime,
+        ConnectParticles, Curve, CurvePoint, Debris, DebrisMode, DensityAlpha,
+        DistanceAnimatedIndex, DistanceLod, DistanceTraveled, EmissionControl, EmitOnce,
+        EmitterShape, FlickerModifier, GlobalParticleGravity, GroupId, GroupLimits,
+        InitialRotation, InitialRotationMode, JitteredValue, Lerp, Lifetime, Line, LoopCount,
+        Noise2D, OffscreenDespawn, ParentTint, Particle, ParticleBurst, ParticleBurstRequest,
+        ParticleConnection, ParticleCount, ParticleLifetimeThreshold, ParticleOutline,
+        ParticleOutlineSprite, ParticlePositions, ParticleQualitySettings, ParticleRegion,
+        ParticleSpace, ParticleSystem, ParticleSystemBundle, ParticleSystemGroup,
+        ParticleSystemPlugin, ParticleSystemStat, ParticleSystemStats, ParticleSystemTween,
+        ParticleTemplates, ParticleTexture, ParticleWind, Playing, RandomValue,
+        ReconcileParticleCounts, RepeatingBurst, RunningState, SeekTo, SinWave, SpawnFilter,
+        SystemVisibility, ValueOverTime, VectorOverTime, Velocity, VelocityConstraint,
+        VelocityModifier,
+    };
+
+    fn test_app() -> App {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .init_resource::<Time<Real>>()
+            .add_plugins((TransformPlugin, ParticleSystemPlugin));
+        app
+    }
+
+    fn spawn_system(app: &mut App, particle_system: ParticleSystem) -> Entity {
+        app.world_mut()
+            .spawn(ParticleSystemBundle {
+                particle_system,
+                ..ParticleSystemBundle::default()
+            })
+            .insert(Playing)
+            .id()
+    }
+
+    fn step(app: &mut App, seconds: f32) {
+        let delta = Duration::from_secs_f32(seconds);
+        app.world_mut().resource_mut::<Time>().advance_by(delta);
+        app.world_mut()
+            .resource_mut::<Time<Real>>()
+            .advance_by(delta);
+        app.update();
+    }
+
+    fn particle_count(app: &mut App) -> usize {
+        app.world_mut()
+            .query::<&Particle>()
+            .iter(app.world())
+            .count()
+    }
+
+    #[test]
+    fn total_particles_to_emit_limits_spawns() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                max_particles: 1000,
+                spawn_rate_per_second: 100.0.into(),
+                lifetime: 100.0.into(),
+                system_duration_seconds: 1.0,
+                total_particles_to_emit: Some(20),
+                bursts: vec![ParticleBurst::new(0.5, 50)],
+                ..ParticleSystem::default()
+            },
+        );
+
+        for _ in 0..50 {
+            step(&mut app, 0.1);
+        }
+
+        assert_eq!(particle_count(&mut app), 20);
+        let running_state = app.world().get::<RunningState>(system).unwrap();
+        assert_eq!(running_state.total_spawned, 20);
+    }
+
+    #[test]
+    fn max_spawns_per_frame_spreads_bursts_over_frames() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                max_particles: 1000,
+                spawn_rate_per_second: 0.0.into(),
+                lifetime: 100.0.into(),
+                system_duration_seconds: 0.5,
+                looping: false,
+                despawn_on_finish: false,
+                bursts: vec![ParticleBurst::new(0.0, 100)],
+                max_spawns_per_frame: Some(10),
+                ..ParticleSystem::default()
+            },
+        );
+
+        for frame in 1..=10 {
+            step(&mut app, 0.1);
+            assert_eq!(particle_count(&mut app), frame * 10);
+        }
+        step(&mut app, 0.1);
+        assert_eq!(particle_count(&mut app), 100);
+        let running_state = app.world().get::<RunningState>(system).unwrap();
+        assert_eq!(running_state.pending_spawns, 0);
+    }
+
+    #[test]
+    fn spread_direction_stays_within_cone() {
+        let mut rng = rand::thread_rng();
+        let base = Vec3::new(2.0, 0.0, 0.0);
+        let mut left_plane = false;
+        for _ in 0..1000 {
+            let direction = spread_direction(base, 0.3, &mut rng);
+            assert!(direction.angle_between(base) <= 0.3 + 1e-4);
+            assert!((direction.length() - 2.0).abs() < 1e-4);
+            left_plane |= direction.z.abs() > 0.05;
+        }
+        assert!(
+            left_plane,
+            "directions should be spread around the whole cone"
+        );
+    }
+
+    #[test]
+    fn spread_direction_pi_covers_sphere() {
+        let mut rng = rand::thread_rng();
+        let backwards = (0..1000)
+            .map(|_| spread_direction(Vec3::X, PI, &mut rng))
+            .filter(|direction| direction.x < -0.5)
+            .count();
+        // Directions more than 120 degrees away from the axis cover a quarter of the sphere.
+        assert!(
+            (150..=350).contains(&backwards),
+            "unexpected count {backwards}"
+        );
+    }
+
+    #[test]
+    fn spread_direction_zero_keeps_direction() {
+        let mut rng = rand::thread_rng();
+        assert_eq!(spread_direction(Vec3::Y, 0.0, &mut rng), Vec3::Y);
+    }
+
+    #[test]
+    fn initial_velocity_override_replaces_emitter_direction() {
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                emitter_shape: EmitterShape::circle(10.0),
+                spawn_rate_per_second: 100.0.into(),
+                initial_speed: 5.0.into(),
+                initial_velocity_override: Some(Vec3::Y.into()),
+                ..ParticleSystem::default()
+            },
+        );
+
+        step(&mut app, 0.5);
+
+        let mut query = app.world_mut().query::<&Velocity>();
+        assert!(query.iter(app.world()).count() > 0);
+        for velocity in query.iter(app.world()) {
+            assert!(velocity.0.abs_diff_eq(Vec3::new(0.0, 5.0, 0.0), 1e-5));
+        }
+    }
+
+    #[test]
+    fn initial_velocity_picks_from_candidates() {
+        let candidates = [Vec3::new(3.0, 0.0, 0.0), Vec3::new(0.0, -2.0, 0.0)];
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                emitter_shape: EmitterShape::circle(10.0),
+                spawn_rate_per_second: 200.0.into(),
+                initial_speed: 50.0.into(),
+                direction_spread: Some(1.0),
+                initial_velocity_override: Some(Vec3::Y.into()),
+                initial_velocity: Some(vec![candidates[0], candidates[1]].into()),
+                ..ParticleSystem::default()
+            },
+        );
+
+        step(&mut app, 0.5);
+
+        let mut seen = [false; 2];
+        let mut query = app.world_mut().query::<&Velocity>();
+        for velocity in query.iter(app.world()) {
+            let index = candidates
+                .iter()
+                .position(|candidate| velocity.0.abs_diff_eq(*candidate, 1e-5))
+                .expect("velocity should be one of the candidates");
+            seen[index] = true;
+        }
+        assert_eq!(seen, [true, true]);
+    }
+
+    #[test]
+    fn steady_state_emission_converges_on_target() {
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                max_particles: 1000,
+                emission_control: EmissionControl::SteadyState { target_alive: 50 },
+                lifetime: JitteredValue::jittered(1.0, -0.5..0.5),
+                system_duration_seconds: 10.0,
+                ..ParticleSystem::default()
+            },
+        );
+
+        for _ in 0..180 {
+            step(&mut app, 1.0 / 60.0);
+        }
+
+        for _ in 0..60 {
+            step(&mut app, 1.0 / 60.0);
+            let count = particle_count(&mut app);
+            assert!((35..=50).contains(&count), "unexpected live count {count}");
+        }
+    }
+
+    #[test]
+    fn align_to_velocity_composes_with_spin() {
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 1.0.into(),
+                initial_speed: 10.0.into(),
+                initial_velocity_override: Some(Vec3::X.into()),
+                velocity_modifiers: vec![VelocityModifier::Vector(
+                    Vec3::new(0.0, 20.0, 0.0).into(),
+                )],
+                rotation_speed: 1.0.into(),
+                align_to_velocity: true,
+                ..ParticleSystem::default()
+            },
+        );
+
+        step(&mut app, 0.1);
+        for _ in 0..5 {
+            step(&mut app, 0.1);
+        }
+
+        let mut query = app
+            .world_mut()
+            .query::<(&Particle, &Velocity, &Transform)>();
+        let (particle, velocity, transform) = query.single(app.world());
+        let heading = velocity.0.y.atan2(velocity.0.x);
+        let spin = particle.initial_rotation + particle.rotation_speed * 0.5;
+        assert!(heading > 0.1);
+        let expected = Quat::from_rotation_z(heading + spin);
+        assert!(transform.rotation.angle_between(expected) < 1e-3);
+    }
+
+    #[test]
+    fn offscreen_particles_are_despawned() {
+        let mut app = test_app();
+        app.world_mut().spawn((
+            Camera::default(),
+            Frustum::from_clip_from_world(&Mat4::orthographic_rh(
+                -100.0, 100.0, -100.0, 100.0, -1000.0, 1000.0,
+            )),
+        ));
+
+        let offscreen = ParticleSystem {
+            spawn_rate_per_second: 1.0.into(),
+            initial_speed: 0.0.into(),
+            lifetime: 100.0.into(),
+            despawn_when_offscreen: Some(OffscreenDespawn {
+                margin: 10.0,
+                grace_period: 0.2,
+            }),
+            ..ParticleSystem::default()
+        };
+        let visible_system = spawn_system(&mut app, offscreen.clone());
+        let hidden_system = spawn_system(&mut app, offscreen);
+        app.world_mut()
+            .entity_mut(hidden_system)
+            .insert(Transform::from_xyz(500.0, 0.0, 0.0));
+
+        step(&mut app, 0.1);
+        assert_eq!(particle_count(&mut app), 2);
+
+        step(&mut app, 0.5);
+        step(&mut app, 0.1);
+
+        let mut query = app.world_mut().query::<&Particle>();
+        let parents: Vec<Entity> = query.iter(app.world()).map(|p| p.parent_system).collect();
+        assert_eq!(parents, vec![visible_system]);
+    }
+
+    #[test]
+    fn offscreen_despawn_keeps_particles_without_active_camera() {
+        let mut app = test_app();
+        app.world_mut().spawn((
+            Camera {
+                is_active: false,
+                ..Camera::default()
+            },
+            Frustum::from_clip_from_world(&Mat4::orthographic_rh(
+                400.0, 600.0, -100.0, 100.0, -1000.0, 1000.0,
+            )),
+        ));
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 1.0.into(),
+                initial_speed: 0.0.into(),
+                lifetime: 100.0.into(),
+                despawn_when_offscreen: Some(OffscreenDespawn {
+                    margin: 0.0,
+                    grace_period: 0.2,
+                }),
+                ..ParticleSystem::default()
+            },
+        );
+
+        step(&mut app, 0.1);
+        step(&mut app, 0.5);
+        step(&mut app, 0.1);
+
+        assert!(particle_count(&mut app) > 0);
+    }
+
+    #[test]
+    fn expired_offscreen_particles_are_counted_once() {
+        let mut app = test_app();
+        // The camera looks away from the emitter, so particles are offscreen as soon as their grace period ends, on
+        // the same frame they expire.
+        app.world_mut().spawn((
+            Camera::default(),
+            Frustum::from_clip_from_world(&Mat4::orthographic_rh(
+                400.0, 600.0, -100.0, 100.0, -1000.0, 1000.0,
+            )),
+        ));
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 10.0.into(),
+                initial_speed: 0.0.into(),
+                lifetime: 0.55.into(),
+                despawn_when_offscreen: Some(OffscreenDespawn {
+                    margin: 0.0,
+                    grace_period: 0.55,
+                }),
+                ..ParticleSystem::default()
+            },
+        );
+
+        for _ in 0..20 {
+            step(&mut app, 0.1);
+        }
+
+        let live = particle_count(&mut app);
+        assert!(live > 0);
+        assert_eq!(app.world().get::<ParticleCount>(system).unwrap().0, live);
+    }
+
+    #[test]
+    fn parent_tint_multiplies_particle_color() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 10.0.into(),
+                color: Color::srgb(0.5, 1.0, 1.0).into(),
+                ..ParticleSystem::default()
+            },
+        );
+        app.world_mut()
+            .entity_mut(system)
+            .insert(ParentTint(Color::srgb(1.0, 0.0, 0.0)));
+
+        step(&mut app, 0.1);
+        step(&mut app, 0.1);
+
+        let expected = Color::srgb(0.5, 0.0, 0.0).to_linear();
+        let mut query = app.world_mut().query::<&Sprite>();
+        assert!(query.iter(app.world()).count() > 0);
+        for sprite in query.iter(app.world()) {
+            let color: LinearRgba = sprite.color.to_linear();
+            assert!((color.red - expected.red).abs() < 1e-5);
+            assert!(color.green.abs() < 1e-5);
+            assert!(color.blue.abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn orbit_modifier_pushes_particles_tangentially() {
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 1.0.into(),
+                emitter_shape: CircleSegment {
+                    opening_angle: 0.0,
+                    direction_angle: 0.0,
+                    radius: 10.0.into(),
+                }
+                .into(),
+                initial_speed: 0.0.into(),
+                velocity_modifiers: vec![VelocityModifier::Orbit {
+                    center: Vec3::ZERO,
+                    strength: 10.0.into(),
+                    axis: Vec3::Z,
+                }],
+                ..ParticleSystem::default()
+            },
+        );
+
+        step(&mut app, 0.05);
+        step(&mut app, 0.05);
+
+        let mut query = app.world_mut().query::<&Velocity>();
+        let velocity = query.single(app.world()).0;
+        assert!(velocity.y > 0.0);
+        assert!(velocity.x.abs() < velocity.y * 0.1);
+    }
+
+    #[test]
+    fn quality_settings_scale_spawn_counts() {
+        let spawned_with_scale = |scale: f32| {
+            let mut app = test_app();
+            app.insert_resource(ParticleQualitySettings {
+                spawn_rate_scale: scale,
+                max_particles_scale: 1.0,
+            });
+            let system = spawn_system(
+                &mut app,
+                ParticleSystem {
+                    max_particles: 1000,
+                    spawn_rate_per_second: 100.0.into(),
+                    lifetime: 100.0.into(),
+                    system_duration_seconds: 10.0,
+                    bursts: vec![ParticleBurst::new(0.5, 40), ParticleBurst::new(0.6, 40)],
+                    ..ParticleSystem::default()
+                },
+            );
+            for _ in 0..20 {
+                step(&mut app, 0.05);
+            }
+            let burst_index = app.world().get::<BurstIndex>(system).unwrap().0;
+            (particle_count(&mut app), burst_index)
+        };
+
+        let (full, full_bursts) = spawned_with_scale(1.0);
+        let (half, half_bursts) = spawned_with_scale(0.5);
+        assert_eq!(full_bursts, 2);
+        assert_eq!(half_bursts, 2);
+        assert!(half.abs_diff(full / 2) <= 2, "{half} is not half of {full}");
+        assert_eq!(spawned_with_scale(0.0).0, 0);
+    }
+
+    #[test]
+    fn distance_animated_index_follows_distance() {
+        let mut app = test_app();
+        let indices = DistanceAnimatedIndex {
+            indices: (0..10).collect(),
+            distance_step: 2.0,
+        };
+        let moved = app
+            .world_mut()
+            .spawn((
+                Lifetime(0.0),
+                DistanceTraveled {
+                    dist_squared: 7.0 * 7.0,
+                    from: Vec3::ZERO,
+                },
+                TextureAtlas::default(),
+                indices.clone(),
+            ))
+            .id();
+        let stationary = app
+            .world_mut()
+            .spawn((
+                Lifetime(0.0),
+                DistanceTraveled::default(),
+                TextureAtlas::default(),
+                indices,
+            ))
+            .id();
+
+        step(&mut app, 0.1);
+
+        assert_eq!(app.world().get::<TextureAtlas>(moved).unwrap().index, 3);
+        assert_eq!(
+            app.world().get::<TextureAtlas>(stationary).unwrap().index,
+            0
+        );
+    }
+
+    #[test]
+    fn distance_animated_index_without_indices_uses_first_frame() {
+        let distance_index = DistanceAnimatedIndex {
+            indices: vec![],
+            distance_step: 2.0,
+        };
+        assert_eq!(distance_index.get_at_distance(7.0), 0);
+    }
+
+    #[test]
+    fn particles_share_sprite_settings() {
+        let mut app = test_app();
+        let texture = Handle::<Image>::weak_from_u128(42);
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                texture: texture.clone().into(),
+                rescale_texture: Some(Vec2::splat(4.0)),
+                spawn_rate_per_second: 100.0.into(),
+                ..ParticleSystem::default()
+            },
+        );
+
+        step(&mut app, 0.5);
+
+        let mut query = app.world_mut().query::<(&Sprite, &Handle<Image>)>();
+        assert!(query.iter(app.world()).count() > 1);
+        for (sprite, image) in query.iter(app.world()) {
+            assert_eq!(image, &texture);
+            assert_eq!(sprite.custom_size, Some(Vec2::splat(4.0)));
+            assert!(!sprite.flip_x && !sprite.flip_y);
+        }
+    }
+
+    #[test]
+    fn initial_age_varies_spawned_lifetimes() {
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 100.0.into(),
+                lifetime: 2.0.into(),
+                initial_age: JitteredValue::jittered(1.0, -1.0..5.0),
+                ..ParticleSystem::default()
+            },
+        );
+
+        step(&mut app, 0.2);
+
+        let mut query = app.world_mut().query::<(&Particle, &Lifetime)>();
+        let ages: Vec<f32> = query.iter(app.world()).map(|(_, l)| l.0).collect();
+        assert!(ages.len() > 1);
+        assert!(ages.iter().all(|age| *age >= 0.0 && *age < 2.0 + 0.2));
+        assert!(ages.iter().any(|age| (age - ages[0]).abs() > f32::EPSILON));
+    }
+
+    #[test]
+    fn global_gravity_modifier_follows_resource() {
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 1.0.into(),
+                initial_speed: 0.0.into(),
+                velocity_modifiers: vec![VelocityModifier::GlobalGravity(2.0)],
+                ..ParticleSystem::default()
+            },
+        );
+
+        step(&mut app, 0.1);
+        step(&mut app, 0.1);
+        let velocity = |app: &mut App| app.world_mut().query::<&Velocity>().single(app.world()).0;
+        assert_eq!(velocity(&mut app), Vec3::ZERO);
+
+        app.insert_resource(GlobalParticleGravity(Vec3::new(0.0, -10.0, 0.0)));
+        let before = velocity(&mut app);
+        step(&mut app, 0.1);
+        let after = velocity(&mut app);
+        assert!((after - before).abs_diff_eq(Vec3::new(0.0, -2.0, 0.0), 1e-4));
+    }
+
+    #[test]
+    fn spawn_grid_snap_rounds_positions() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 100.0.into(),
+                emitter_shape: EmitterShape::circle(JitteredValue::jittered(20.0, -10.0..10.0)),
+                initial_speed: 0.0.into(),
+                spawn_grid_snap: Some(8.0),
+                ..ParticleSystem::default()
+            },
+        );
+        app.world_mut()
+            .entity_mut(system)
+            .insert(Transform::from_xyz(3.0, 5.0, 0.0));
+
+        step(&mut app, 0.5);
+
+        let mut query = app.world_mut().query::<(&Particle, &Transform)>();
+        assert!(query.iter(app.world()).count() > 1);
+        for (_, transform) in query.iter(app.world()) {
+            assert_relative_eq!(transform.translation.x % 8.0, 0.0);
+            assert_relative_eq!(transform.translation.y % 8.0, 0.0);
+        }
+    }
+
+    #[test]
+    fn z_offset_from_emitter_follows_emitter_depth() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 10.0.into(),
+                initial_speed: 0.0.into(),
+                z_offset_from_emitter: Some(-0.5),
+                ..ParticleSystem::default()
+            },
+        );
+        app.world_mut()
+            .entity_mut(system)
+            .insert(Transform::from_xyz(0.0, 0.0, 4.0));
+
+        // Propagate the emitter transform before anything spawns.
+        step(&mut app, 0.0);
+        step(&mut app, 0.5);
+
+        let mut query = app.world_mut().query::<(&Particle, &Transform)>();
+        assert!(query.iter(app.world()).count() > 0);
+        for (_, transform) in query.iter(app.world()) {
+            assert_relative_eq!(transform.translation.z, 3.5);
+        }
+    }
+
+    #[test]
+    fn z_value_override_takes_precedence_over_z_offset() {
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 10.0.into(),
+                initial_speed: 0.0.into(),
+                z_value_override: Some(1.0.into()),
+                z_offset_from_emitter: Some(-0.5),
+                ..ParticleSystem::default()
+            },
+        );
+
+        step(&mut app, 0.5);
+
+        let mut query = app.world_mut().query::<(&Particle, &Transform)>();
+        assert!(query.iter(app.world()).count() > 0);
+        for (_, transform) in query.iter(app.world()) {
+            assert_relative_eq!(transform.translation.z, 1.0);
+        }
+    }
+
+    #[derive(Component)]
+    struct MovingParent;
+
+    fn move_parent(mut parents: Query<&mut Transform, With<MovingParent>>) {
+        for mut transform in &mut parents {
+            transform.translation.x += 10.0;
+        }
+    }
+
+    fn assert_spawns_without_lag(space: ParticleSpace) {
+        let mut app = test_app();
+        app.add_systems(Update, move_parent);
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 100.0.into(),
+                initial_speed: 0.0.into(),
+                space,
+                ..ParticleSystem::default()
+            },
+        );
+        app.world_mut()
+            .spawn((MovingParent, TransformBundle::default()))
+            .add_child(system);
+
+        for _ in 0..5 {
+            step(&mut app, 0.05);
+            let parent_x = app
+                .world_mut()
+                .query_filtered::<&Transform, With<MovingParent>>()
+                .single(app.world())
+                .translation
+                .x;
+
+            // Particles spawned this frame haven't been aged yet.
+            let mut query = app
+                .world_mut()
+                .query::<(&Lifetime, &Transform, &GlobalTransform)>();
+            let mut fresh = 0;
+            for (lifetime, transform, global_transform) in query.iter(app.world()) {
+                if lifetime.0 > 0.0 {
+                    continue;
+                }
+                fresh += 1;
+                assert_relative_eq!(global_transform.translation().x, parent_x);
+                if matches!(space, ParticleSpace::World) {
+                    assert_relative_eq!(transform.translation.x, parent_x);
+                }
+            }
+            assert!(fresh > 0);
+        }
+    }
+
+    #[test]
+    fn world_space_particles_spawn_at_current_emitter_position() {
+        assert_spawns_without_lag(ParticleSpace::World);
+    }
+
+    #[test]
+    fn local_space_particles_spawn_at_current_emitter_position() {
+        assert_spawns_without_lag(ParticleSpace::Local);
+    }
+
+    #[test]
+    fn outlines_follow_and_despawn_with_their_particles() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 10.0.into(),
+                lifetime: 0.5.into(),
+                initial_speed: 0.0.into(),
+                outline: Some(ParticleOutline {
+                    color: Color::BLACK,
+                    thickness: 0.25,
+                }),
+                ..ParticleSystem::default()
+            },
+        );
+
+        step(&mut app, 0.3);
+
+        let particles = particle_count(&mut app);
+        assert!(particles > 0);
+        let mut query = app
+            .world_mut()
+            .query::<(&ParticleOutlineSprite, &Parent, &Transform)>();
+        let outlines: Vec<_> = query
+            .iter(app.world())
+            .map(|(_, parent, transform)| (parent.get(), *transform))
+            .collect();
+        assert_eq!(outlines.len(), particles);
+        for (parent, transform) in outlines {
+            assert!(app.world().get::<Particle>(parent).is_some());
+            assert!(transform.scale.abs_diff_eq(Vec3::splat(1.25), 1e-6));
+            assert!(transform.translation.z < 0.0);
+        }
+
+        app.world_mut().entity_mut(system).remove::<Playing>();
+        for _ in 0..10 {
+            step(&mut app, 0.1);
+        }
+
+        assert_eq!(particle_count(&mut app), 0);
+        let mut query = app.world_mut().query::<&ParticleOutlineSprite>();
+        assert_eq!(query.iter(app.world()).count(), 0);
+    }
+
+    #[test]
+    fn particles_despawn_when_leaving_region() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 20.0.into(),
+                lifetime: 10.0.into(),
+                initial_speed: 100.0.into(),
+                despawn_region: Some(ParticleRegion::Aabb {
+                    min: Vec3::new(-20.0, -20.0, -1.0),
+                    max: Vec3::new(20.0, 20.0, 1.0),
+                }),
+                ..ParticleSystem::default()
+            },
+        );
+
+        for _ in 0..20 {
+            step(&mut app, 0.05);
+            let mut query = app.world_mut().query::<(&Particle, &Transform)>();
+            for (_, transform) in query.iter(app.world()) {
+                assert!(transform.translation.x.abs() <= 25.0);
+                assert!(transform.translation.y.abs() <= 25.0);
+            }
+        }
+        assert!(particle_count(&mut app) < 20);
+
+        // Every particle moves at 100 units per second, so none can stay in the region for long.
+        app.world_mut().entity_mut(system).remove::<Playing>();
+        for _ in 0..10 {
+            step(&mut app, 0.05);
+        }
+        assert_eq!(particle_count(&mut app), 0);
+    }
+
+    #[test]
+    fn particles_spawned_outside_region_respect_flag() {
+        for despawn_if_spawned_outside in [false, true] {
+            let mut app = test_app();
+            let system = spawn_system(
+                &mut app,
+                ParticleSystem {
+                    spawn_rate_per_second: 10.0.into(),
+                    lifetime: 10.0.into(),
+                    initial_speed: 0.0.into(),
+                    despawn_region: Some(ParticleRegion::Sphere {
+                        center: Vec3::new(50.0, 0.0, 0.0),
+                        radius: 10.0,
+                    }),
+                    despawn_if_spawned_outside,
+                    ..ParticleSystem::default()
+                },
+            );
+
+            for _ in 0..5 {
+                step(&mut app, 0.1);
+            }
+            app.world_mut().entity_mut(system).remove::<Playing>();
+            step(&mut app, 0.1);
+
+            assert_eq!(particle_count(&mut app) == 0, despawn_if_spawned_outside);
+        }
+    }
+
+    #[test]
+    fn velocity_constraint_locks_axes() {
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 1.0.into(),
+                lifetime: 10.0.into(),
+                initial_speed: 50.0.into(),
+                // A diagonal direction, so the particle always has some movement along x.
+                initial_velocity_override: Some(VectorOverTime::Constant(Vec3::new(1.0, 1.0, 0.0))),
+                velocity_modifiers: vec![VelocityModifier::Vector(VectorOverTime::Constant(
+                    Vec3::new(0.0, 500.0, 0.0),
+                ))],
+                velocity_constraint: Some(VelocityConstraint {
+                    lock_y: true,
+                    ..VelocityConstraint::default()
+                }),
+                ..ParticleSystem::default()
+            },
+        );
+
+        step(&mut app, 0.1);
+        let mut query = app.world_mut().query::<(Entity, &Particle, &Transform)>();
+        let (particle, _, transform) = query.single(app.world());
+        let start = transform.translation;
+
+        for _ in 0..10 {
+            step(&mut app, 0.1);
+            let transform = app.world().get::<Transform>(particle).unwrap();
+            assert_relative_eq!(transform.translation.y, start.y);
+            assert_relative_eq!(app.world().get::<Velocity>(particle).unwrap().0.y, 0.0);
+        }
+        let transform = app.world().get::<Transform>(particle).unwrap();
+        assert!(transform.translation.distance(start) > 1.0);
+    }
+
+    #[test]
+    fn repeating_bursts_fire_every_interval() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                max_particles: 1000,
+                spawn_rate_per_second: 0.0.into(),
+                lifetime: 100.0.into(),
+                system_duration_seconds: 10.0,
+                bursts: vec![ParticleBurst::new(0.5, 10)],
+                repeating_burst: Some(RepeatingBurst::new(0.25, 3)),
+                ..ParticleSystem::default()
+            },
+        );
+
+        for _ in 0..8 {
+            step(&mut app, 0.125);
+        }
+
+        // Repeating bursts at 0.0, 0.25, 0.5, 0.75 and 1.0, plus the explicit burst.
+        let running_state = app.world().get::<RunningState>(system).unwrap();
+        assert_eq!(running_state.repeating_bursts_fired, 5);
+        assert_eq!(particle_count(&mut app), 5 * 3 + 10);
+    }
+
+    #[test]
+    fn repeating_burst_jitter_stays_in_range() {
+        let repeating_burst = RepeatingBurst {
+            jitter: 2,
+            ..RepeatingBurst::new(0.1, 3)
+        };
+        let mut rng = StdRng::seed_from_u64(7);
+        let counts: Vec<usize> = (0..200)
+            .map(|_| repeating_burst.get_count(&mut rng))
+            .collect();
+
+        assert!(counts.iter().all(|count| (1..=5).contains(count)));
+        assert!(counts.contains(&1) && counts.contains(&5));
+    }
+
+    #[test]
+    fn tween_interpolates_then_snaps_to_target() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 10.0.into(),
+                initial_speed: JitteredValue::jittered(20.0, 0.0..10.0),
+                looping: true,
+                ..ParticleSystem::default()
+            },
+        );
+        app.world_mut()
+            .entity_mut(system)
+            .insert(ParticleSystemTween::new(
+                ParticleSystem {
+                    spawn_rate_per_second: 100.0.into(),
+                    initial_speed: JitteredValue::jittered(40.0, 0.0..30.0),
+                    looping: false,
+                    ..ParticleSystem::default()
+                },
+                1.0,
+            ));
+
+        step(&mut app, 0.25);
+        step(&mut app, 0.25);
+
+        let particle_system = app.world().get::<ParticleSystem>(system).unwrap();
+        assert_relative_eq!(
+            particle_system.spawn_rate_per_second.at_lifetime_pct(0.0),
+            55.0
+        );
+        assert_relative_eq!(particle_system.initial_speed.value, 30.0);
+        assert_eq!(particle_system.initial_speed.jitter_range, Some(0.0..20.0));
+        // Values that can't be interpolated keep their start value until the end.
+        assert!(particle_system.looping);
+
+        step(&mut app, 0.25);
+        let particle_system = app.world().get::<ParticleSystem>(system).unwrap();
+        assert_relative_eq!(
+            particle_system.spawn_rate_per_second.at_lifetime_pct(0.0),
+            77.5
+        );
+
+        step(&mut app, 0.25);
+        let particle_system = app.world().get::<ParticleSystem>(system).unwrap();
+        assert_relative_eq!(
+            particle_system.spawn_rate_per_second.at_lifetime_pct(0.0),
+            100.0
+        );
+        assert!(!particle_system.looping);
+        assert!(app.world().get::<ParticleSystemTween>(system).is_none());
+    }
+
+    #[test]
+    fn tween_only_changes_the_system_when_values_move() {
+        let mut app = test_app();
+        let system = spawn_system(&mut app, ParticleSystem::default());
+        app.world_mut()
+            .entity_mut(system)
+            .insert(ParticleSystemTween::new(ParticleSystem::default(), 1.0));
+        let last_changed = |app: &App| {
+            app.world()
+                .entity(system)
+                .get_change_ticks::<ParticleSystem>()
+                .unwrap()
+                .last_changed_tick()
+        };
+
+        step(&mut app, 0.25);
+        let before = last_changed(&app);
+        step(&mut app, 0.25);
+        assert_eq!(last_changed(&app), before);
+
+        step(&mut app, 0.5);
+        assert!(app.world().get::<ParticleSystemTween>(system).is_none());
+        let finished = last_changed(&app);
+        step(&mut app, 0.25);
+        assert_eq!(last_changed(&app), finished);
+    }
+
+    #[test]
+    fn bursts_sharing_a_time_fire_together() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                max_particles: 1000,
+                spawn_rate_per_second: 0.0.into(),
+                lifetime: 100.0.into(),
+                bursts: vec![
+                    ParticleBurst::new(0.0, 5),
+                    ParticleBurst::new(0.0, 7),
+                    ParticleBurst::new(0.0, 11),
+                    ParticleBurst::new(0.5, 13),
+                    ParticleBurst::new(0.5, 17),
+                ],
+                ..ParticleSystem::default()
+            },
+        );
+
+        step(&mut app, 0.0);
+        assert_eq!(particle_count(&mut app), 5 + 7 + 11);
+        assert_eq!(app.world().get::<BurstIndex>(system).unwrap().0, 3);
+
+        // A single long frame passing both remaining bursts fires them both.
+        step(&mut app, 1.0);
+        assert_eq!(particle_count(&mut app), 5 + 7 + 11 + 13 + 17);
+    }
+
+    #[test]
+    fn reconciliation_corrects_externally_despawned_particles() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 0.0.into(),
+                lifetime: 100.0.into(),
+                bursts: vec![ParticleBurst::new(0.0, 10)],
+                ..ParticleSystem::default()
+            },
+        );
+        step(&mut app, 0.1);
+        assert_eq!(app.world().get::<ParticleCount>(system).unwrap().0, 10);
+
+        let despawn_one = |app: &mut App| {
+            let mut query = app.world_mut().query_filtered::<Entity, With<Particle>>();
+            let particle = query.iter(app.world()).next().unwrap();
+            app.world_mut().entity_mut(particle).despawn_recursive();
+        };
+
+        // Counts aren't reconciled by default.
+        despawn_one(&mut app);
+        step(&mut app, 0.1);
+        assert_eq!(app.world().get::<ParticleCount>(system).unwrap().0, 10);
+
+        app.insert_resource(ReconcileParticleCounts);
+        despawn_one(&mut app);
+        step(&mut app, 0.1);
+        assert_eq!(particle_count(&mut app), 8);
+        assert_eq!(app.world().get::<ParticleCount>(system).unwrap().0, 8);
+    }
+
+    #[test]
+    fn resuming_after_a_long_frame_does_not_spike_spawns() {
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                max_particles: 10_000,
+                spawn_rate_per_second: 100.0.into(),
+                lifetime: 100.0.into(),
+                system_duration_seconds: 100.0,
+                ..ParticleSystem::default()
+            },
+        );
+
+        step(&mut app, 0.1);
+        let before_pause = particle_count(&mut app);
+
+        // Paused frames don't advance time, then the first frame after resuming covers the whole pause.
+        for _ in 0..10 {
+            step(&mut app, 0.0);
+        }
+        assert_eq!(particle_count(&mut app), before_pause);
+        step(&mut app, 10.0);
+
+        let spawned = particle_count(&mut app) - before_pause;
+        assert!(
+            spawned <= 26,
+            "{spawned} particles spawned in a single frame"
+        );
+
+        step(&mut app, 0.1);
+        assert_eq!(particle_count(&mut app) - before_pause - spawned, 10);
+    }
+
+    #[test]
+    fn distance_lod_reduces_spawn_rate_of_far_systems() {
+        let mut app = test_app();
+        app.world_mut()
+            .spawn((Camera::default(), TransformBundle::default()));
+        let far_camera = app
+            .world_mut()
+            .spawn((
+                Camera {
+                    is_active: false,
+                    ..Camera::default()
+                },
+                TransformBundle::from_transform(Transform::from_xyz(2000.0, 0.0, 0.0)),
+            ))
+            .id();
+
+        let lod_system = |app: &mut App, x: f32, lod: DistanceLod| {
+            let system = spawn_system(
+                app,
+                ParticleSystem {
+                    max_particles: 1000,
+                    spawn_rate_per_second: 100.0.into(),
+                    lifetime: 100.0.into(),
+                    initial_speed: 0.0.into(),
+                    system_duration_seconds: 100.0,
+                    ..ParticleSystem::default()
+                },
+            );
+            app.world_mut()
+                .entity_mut(system)
+                .insert((Transform::from_xyz(x, 0.0, 0.0), lod));
+            system
+        };
+        let near = lod_system(&mut app, 50.0, DistanceLod::new(100.0, 1000.0, 0.1));
+        let halfway = lod_system(&mut app, 550.0, DistanceLod::new(100.0, 1000.0, 0.1));
+        let far = lod_system(&mut app, 2000.0, DistanceLod::new(100.0, 1000.0, 0.1));
+        let culled = lod_system(
+            &mut app,
+            2000.0,
+            DistanceLod::new(100.0, 1000.0, 0.1).with_cull_beyond_far(),
+        );
+
+        for _ in 0..10 {
+            step(&mut app, 0.1);
+        }
+
+        let count = |app: &App, system| app.world().get::<ParticleCount>(system).unwrap().0;
+        let rate_scale =
+            |app: &App, system| app.world().get::<DistanceLod>(system).unwrap().rate_scale();
+        assert_relative_eq!(rate_scale(&app, near), 1.0);
+        assert_relative_eq!(rate_scale(&app, halfway), 0.55);
+        assert_relative_eq!(rate_scale(&app, far), 0.1);
+        assert_relative_eq!(rate_scale(&app, culled), 0.0);
+        assert!((99..=101).contains(&count(&app, near)));
+        assert!((54..=57).contains(&count(&app, halfway)));
+        assert!((10..=12).contains(&count(&app, far)));
+        assert_eq!(count(&app, culled), 0);
+
+        // The nearest active camera is used.
+        app.world_mut()
+            .entity_mut(far_camera)
+            .get_mut::<Camera>()
+            .unwrap()
+            .is_active = true;
+        step(&mut app, 0.1);
+        assert_relative_eq!(rate_scale(&app, far), 1.0);
+        assert_relative_eq!(rate_scale(&app, near), 1.0);
+    }
+
+    #[test]
+    fn hidden_system_keeps_simulating() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 20.0.into(),
+                initial_speed: 10.0.into(),
+                lifetime: 10.0.into(),
+                ..ParticleSystem::default()
+            },
+        );
+        step(&mut app, 0.2);
+
+        app.world_mut().entity_mut(system).insert(SystemVisibility {
+            simulate: true,
+            render: false,
+        });
+        step(&mut app, 0.1);
+
+        let positions: EntityHashMap<Vec3> = app
+            .world_mut()
+            .query_filtered::<(Entity, &Transform), With<Particle>>()
+            .iter(app.world())
+            .map(|(entity, transform)| (entity, transform.translation))
+            .collect();
+        assert!(!positions.is_empty());
+
+        step(&mut app, 0.1);
+
+        let mut query = app
+            .world_mut()
+            .query_filtered::<(Entity, &Transform, &Visibility), With<Particle>>();
+        assert!(query.iter(app.world()).count() > positions.len());
+        for (entity, transform, visibility) in query.iter(app.world()) {
+            assert_eq!(*visibility, Visibility::Hidden);
+            if let Some(previous) = positions.get(&entity) {
+                assert!(!transform.translation.abs_diff_eq(*previous, 1e-4));
+            }
+        }
+
+        app.world_mut()
+            .entity_mut(system)
+            .insert(SystemVisibility::default());
+        step(&mut app, 0.1);
+        let mut query = app.world_mut().query::<&Visibility>();
+        assert!(query
+            .iter(app.world())
+            .all(|visibility| *visibility == Visibility::Inherited));
+    }
+
+    #[test]
+    fn frozen_system_keeps_particles_in_place() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 20.0.into(),
+                initial_speed: 10.0.into(),
+                lifetime: 10.0.into(),
+                ..ParticleSystem::default()
+            },
+        );
+        step(&mut app, 0.2);
+
+        app.world_mut().entity_mut(system).insert(SystemVisibility {
+            simulate: false,
+            render: true,
+        });
+        let snapshot = |app: &mut App| {
+            let mut particles: Vec<(Vec3, f32)> = app
+                .world_mut()
+                .query::<(&Transform, &Lifetime)>()
+                .iter(app.world())
+                .map(|(transform, lifetime)| (transform.translation, lifetime.0))
+                .collect();
+            particles.sort_by(|a, b| a.1.total_cmp(&b.1));
+            particles
+        };
+        let frozen = snapshot(&mut app);
+        assert!(!frozen.is_empty());
+
+        step(&mut app, 0.5);
+
+        assert_eq!(snapshot(&mut app), frozen);
+    }
+
+    #[test]
+    fn connections_follow_spawn_order() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 20.0.into(),
+                initial_speed: 10.0.into(),
+                lifetime: 10.0.into(),
+                connect_particles: Some(ConnectParticles {
+                    color: Color::WHITE.into(),
+                    width: 2.0,
+                }),
+                ..ParticleSystem::default()
+            },
+        );
+        for _ in 0..5 {
+            step(&mut app, 0.1);
+        }
+        app.world_mut().entity_mut(system).remove::<Playing>();
+        step(&mut app, 0.1);
+
+        let connections = |app: &mut App| -> EntityHashMap<Entity> {
+            app.world_mut()
+                .query::<&ParticleConnection>()
+                .iter(app.world())
+                .map(|connection| (connection.from, connection.to))
+                .collect()
+        };
+        let links = connections(&mut app);
+        let particles = particle_count(&mut app);
+        assert!(particles > 2);
+        assert_eq!(links.len(), particles - 1);
+
+        // Walking the links from the oldest particle visits every particle, from oldest to newest.
+        let lifetime = |app: &App, entity: Entity| app.world().get::<Lifetime>(entity).unwrap().0;
+        let mut current = *links
+            .keys()
+            .find(|from| !links.values().any(|to| to == *from))
+            .unwrap();
+        let mut path = vec![current];
+        while let Some(&next) = links.get(&current) {
+            assert!(lifetime(&app, next) <= lifetime(&app, current));
+            path.push(next);
+            current = next;
+        }
+        assert_eq!(path.len(), particles);
+
+        // Despawning a particle in the middle breaks the path around it.
+        let middle = path[path.len() / 2];
+        app.world_mut().despawn(middle);
+        step(&mut app, 0.1);
+        let links = connections(&mut app);
+        assert_eq!(links.len(), particles - 3);
+        assert!(!links.contains_key(&middle));
+        assert!(!links.values().any(|to| *to == middle));
+    }
+
+    #[test]
+    fn lifetime_by_speed_scales_lifetime() {
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 100.0.into(),
+                initial_speed: JitteredValue::jittered(5.0, 0.0..5.0),
+                lifetime: 1.0.into(),
+                lifetime_by_speed: Some(Curve::new(vec![
+                    CurvePoint::new(1.0, 0.0),
+                    CurvePoint::new(2.0, 1.0),
+                ])),
+                ..ParticleSystem::default()
+            },
+        );
+
+        step(&mut app, 0.2);
+
+        let mut query = app.world_mut().query::<(&Particle, &Velocity)>();
+        assert!(query.iter(app.world()).count() > 5);
+        for (particle, velocity) in query.iter(app.world()) {
+            // Speeds range from 5 to 10, so lifetimes range from 1.5 to 2 seconds.
+            let speed = velocity.0.length();
+            assert_relative_eq!(particle.max_lifetime, 1.0 + speed / 10.0, epsilon = 1e-4);
+        }
+    }
+
+    #[test]
+    fn spawn_filter_vetoes_particles() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 200.0.into(),
+                emitter_shape: EmitterShape::circle(10.0),
+                initial_speed: 0.0.into(),
+                ..ParticleSystem::default()
+            },
+        );
+        app.world_mut()
+            .entity_mut(system)
+            .insert(SpawnFilter::new(|context| context.position.y <= 0.0));
+
+        step(&mut app, 0.2);
+
+        let mut query = app
+            .world_mut()
+            .query_filtered::<&GlobalTransform, With<Particle>>();
+        let spawned = query.iter(app.world()).count();
+        assert!(spawned > 0);
+        assert!(query
+            .iter(app.world())
+            .all(|transform| transform.translation().y <= 0.0));
+        // Vetoed particles don't take up room in the system.
+        assert_eq!(app.world().get::<ParticleCount>(system).unwrap().0, spawned);
+        assert_eq!(
+            app.world()
+                .get::<RunningState>(system)
+                .unwrap()
+                .total_spawned,
+            spawned
+        );
+    }
+
+    #[test]
+    fn spawn_filter_rejecting_everything_emits_nothing() {
+        let mut app = test_app();
+        let system = spawn_system(&mut app, ParticleSystem::default());
+        app.world_mut()
+            .entity_mut(system)
+            .insert(SpawnFilter::new(|_| false));
+
+        for _ in 0..5 {
+            step(&mut app, 0.5);
+        }
+
+        assert_eq!(particle_count(&mut app), 0);
+        assert_eq!(app.world().get::<ParticleCount>(system).unwrap().0, 0);
+    }
+
+    #[test]
+    fn seeded_spawn_filter_moves_past_vetoed_positions() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 5.0.into(),
+                lifetime: 100.0.into(),
+                emitter_shape: EmitterShape::circle(10.0),
+                initial_speed: 0.0.into(),
+                seed: Some(5),
+                ..ParticleSystem::default()
+            },
+        );
+        let vetoed = Mutex::new(None);
+        app.world_mut()
+            .entity_mut(system)
+            .insert(SpawnFilter::new(move |context| {
+                let mut vetoed = vetoed.lock().unwrap();
+                *vetoed.get_or_insert(context.position) != context.position
+            }));
+
+        step(&mut app, 0.1);
+        assert_eq!(particle_count(&mut app), 0);
+        for _ in 0..5 {
+            step(&mut app, 0.1);
+        }
+        assert!(particle_count(&mut app) > 0);
+    }
+
+    #[test]
+    fn color_layers_composite_onto_base_color() {
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 1.0.into(),
+                lifetime: 1.0.into(),
+                color: LinearRgba::new(0.25, 0.25, 0.25, 1.0).into(),
+                color_layers: vec![(
+                    ColorBlendOp::Add,
+                    // A pulse peaking halfway through the particle's lifetime.
+                    ColorOverTime::gradient([
+                        (0.0, LinearRgba::NONE),
+                        (0.5, LinearRgba::new(1.0, 0.5, 0.0, 0.0)),
+                        (1.0, LinearRgba::NONE),
+                    ]),
+                )],
+                ..ParticleSystem::default()
+            },
+        );
+        // Colors and lifetimes update in the same schedule, so take an empty step to read the color of the new age.
+        let color = |app: &mut App| {
+            step(app, 0.0);
+            app.world_mut()
+                .query_filtered::<&Sprite, With<Particle>>()
+                .single(app.world())
+                .color
+                .to_linear()
+        };
+
+        step(&mut app, 0.0);
+        assert_eq!(color(&mut app), LinearRgba::new(0.25, 0.25, 0.25, 1.0));
+
+        step(&mut app, 0.25);
+        let quarter = color(&mut app);
+        assert_relative_eq!(quarter.red, 0.75, epsilon = 1e-5);
+        assert_relative_eq!(quarter.green, 0.5, epsilon = 1e-5);
+        assert_relative_eq!(quarter.blue, 0.25, epsilon = 1e-5);
+
+        // Channels are clamped once they overshoot.
+        step(&mut app, 0.25);
+        let half = color(&mut app);
+        assert_relative_eq!(half.red, 1.0, epsilon = 1e-5);
+        assert_relative_eq!(half.green, 0.75, epsilon = 1e-5);
+        assert_relative_eq!(half.alpha, 1.0, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn zero_rate_without_bursts_spawns_nothing() {
+        let particle_system = ParticleSystem {
+            spawn_rate_per_second: 0.0.into(),
+            looping: true,
+            ..ParticleSystem::default()
+        };
+        assert!(!particle_system.emits_anything());
+
+        let mut app = test_app();
+        let system = spawn_system(&mut app, particle_system);
+        for _ in 0..100 {
+            step(&mut app, 0.1);
+        }
+
+        assert_eq!(particle_count(&mut app), 0);
+        assert_eq!(
+            app.world()
+                .get::<RunningState>(system)
+                .unwrap()
+                .total_spawned,
+            0
+        );
+        assert!(app.world().get::<Playing>(system).is_some());
+    }
+
+    #[test]
+    fn emitter_wander_shifts_spawn_origin() {
+        let noise = Noise2D::new(1.0, 5.0, Vec2::new(1.0, 0.5));
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 10.0.into(),
+                emitter_shape: EmitterShape::circle(0.0),
+                emitter_wander: Some(noise.clone()),
+                initial_speed: 0.0.into(),
+                lifetime: 10.0.into(),
+                ..ParticleSystem::default()
+            },
+        );
+
+        let mut origins = Vec::new();
+        for _ in 0..3 {
+            step(&mut app, 0.5);
+            let elapsed = app.world().resource::<Time>().elapsed_seconds_wrapped();
+            let expected = noise.sample(Vec2::ZERO, elapsed).extend(0.0);
+
+            // Particles spawned this frame haven't aged yet.
+            let mut query = app.world_mut().query::<(&Lifetime, &Transform)>();
+            let spawned: Vec<Vec3> = query
+                .iter(app.world())
+                .filter(|(lifetime, _)| lifetime.0 < f32::EPSILON)
+                .map(|(_, transform)| transform.translation)
+                .collect();
+            assert!(!spawned.is_empty());
+            for translation in spawned {
+                assert!(translation.abs_diff_eq(expected, 1e-4));
+            }
+            origins.push(expected);
+        }
+
+        assert!(!origins[0].abs_diff_eq(origins[1], 0.1));
+        assert!(!origins[1].abs_diff_eq(origins[2], 0.1));
+    }
+
+    #[test]
+    fn discrete_initial_rotation_picks_from_angles() {
+        let angles = [0.0, FRAC_PI_2, PI, 3.0 * FRAC_PI_2];
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 200.0.into(),
+                initial_rotation: angles.to_vec().into(),
+                ..ParticleSystem::default()
+            },
+        );
+
+        step(&mut app, 0.2);
+
+        let mut seen = [false; 4];
+        let mut query = app.world_mut().query::<&Particle>();
+        for particle in query.iter(app.world()) {
+            let index = angles
+                .iter()
+                .position(|angle| (particle.initial_rotation - angle).abs() < 1e-6)
+                .expect("rotation should be one of the angles");
+            seen[index] = true;
+        }
+        assert_eq!(seen, [true; 4]);
+    }
+
+    #[test]
+    fn face_outward_points_particles_away_from_center() {
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 100.0.into(),
+                emitter_shape: EmitterShape::circle(10.0),
+                initial_rotation_mode: InitialRotationMode::FaceOutward,
+                initial_speed: 0.0.into(),
+                ..ParticleSystem::default()
+            },
+        );
+
+        step(&mut app, 0.2);
+
+        let mut query = app
+            .world_mut()
+            .query_filtered::<&Transform, With<Particle>>();
+        let mut count = 0;
+        for transform in query.iter(app.world()) {
+            let facing = transform.rotation * Vec3::X;
+            let outward = transform.translation.truncate().extend(0.0).normalize();
+            assert!(facing.abs_diff_eq(outward, 1e-4), "{facing} != {outward}");
+            count += 1;
+        }
+        assert!(count > 0);
+    }
+
+    #[test]
+    fn collect_particle_positions_gathers_live_particles() {
+        let mut app = test_app();
+        let still_particles = ParticleSystem {
+            spawn_rate_per_second: 20.0.into(),
+            emitter_shape: EmitterShape::circle(0.0),
+            initial_speed: 0.0.into(),
+            lifetime: 10.0.into(),
+            ..ParticleSystem::default()
+        };
+        let collected = spawn_system(&mut app, still_particles.clone());
+        app.world_mut().entity_mut(collected).insert((
+            Transform::from_xyz(10.0, 20.0, 0.0),
+            CollectParticlePositions,
+        ));
+        let other = spawn_system(&mut app, still_particles);
+        app.world_mut()
+            .entity_mut(other)
+            .insert(Transform::from_xyz(-5.0, 0.0, 0.0));
+
+        for _ in 0..3 {
+            step(&mut app, 0.1);
+        }
+
+        let positions = &app.world().get::<ParticlePositions>(collected).unwrap().0;
+        assert_eq!(
+            positions.len(),
+            app.world().get::<ParticleCount>(collected).unwrap().0
+        );
+        assert!(positions.len() > 1);
+        for position in positions {
+            assert!(position.abs_diff_eq(Vec3::new(10.0, 20.0, 0.0), 1e-5));
+        }
+        assert!(app.world().get::<ParticlePositions>(other).is_none());
+
+        // The list is refilled in place every frame.
+        let capacity = positions.capacity();
+        step(&mut app, 0.1);
+        let positions = &app.world().get::<ParticlePositions>(collected).unwrap().0;
+        assert_eq!(
+            positions.len(),
+            app.world().get::<ParticleCount>(collected).unwrap().0
+        );
+        assert!(positions.capacity() >= capacity);
+    }
+
+    fn deferred_burst_system(deferred_bursts: bool, deferred_burst_timeout: f32) -> ParticleSystem {
+        ParticleSystem {
+            max_particles: 10,
+            spawn_rate_per_second: 0.0.into(),
+            bursts: vec![ParticleBurst::new(0.0, 30)],
+            lifetime: 0.5.into(),
+            system_duration_seconds: 10.0,
+            deferred_bursts,
+            deferred_burst_timeout,
+            ..ParticleSystem::default()
+        }
+    }
+
+    #[test]
+    fn deferred_bursts_emit_as_capacity_frees() {
+        let mut app = test_app();
+        let system = spawn_system(&mut app, deferred_burst_system(true, 10.0));
+        let total_spawned = |app: &App| {
+            app.world()
+                .get::<RunningState>(system)
+                .unwrap()
+                .total_spawned
+        };
+
+        step(&mut app, 0.1);
+        assert_eq!(particle_count(&mut app), 10);
+        assert_eq!(
+            app.world()
+                .get::<RunningState>(system)
+                .unwrap()
+                .deferred_burst_particles,
+            20
+        );
+
+        for _ in 0..20 {
+            step(&mut app, 0.1);
+            assert!(particle_count(&mut app) <= 10);
+        }
+        assert_eq!(total_spawned(&app), 30);
+    }
+
+    #[test]
+    fn bursts_beyond_capacity_are_dropped_without_deferral() {
+        let mut app = test_app();
+        let dropped = spawn_system(&mut app, deferred_burst_system(false, 10.0));
+        // The remainder times out before the first particles die.
+        let timed_out = spawn_system(&mut app, deferred_burst_system(true, 0.2));
+
+        for _ in 0..20 {
+            step(&mut app, 0.1);
+        }
+
+        for system in [dropped, timed_out] {
+            let running_state = app.world().get::<RunningState>(system).unwrap();
+            assert_eq!(running_state.total_spawned, 10);
+            assert_eq!(running_state.deferred_burst_particles, 0);
+        }
+    }
+
+    #[test]
+    fn alpha_over_time_keeps_rgb() {
+        let rgb = LinearRgba::new(0.2, 0.4, 0.6, 1.0);
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 1.0.into(),
+                lifetime: 1.0.into(),
+                color: rgb.into(),
+                alpha_over_time: Some(AlphaOverTime((1.0..0.0).into())),
+                ..ParticleSystem::default()
+            },
+        );
+
+        step(&mut app, 0.0);
+        for expected_alpha in [1.0, 0.75, 0.5, 0.25] {
+            // Colors and lifetimes update in the same schedule, so take an empty step to read the new color.
+            step(&mut app, 0.0);
+            let color = app
+                .world_mut()
+                .query_filtered::<&Sprite, With<Particle>>()
+                .single(app.world())
+                .color
+                .to_linear();
+            assert!(color
+                .with_alpha(1.0)
+                .to_vec4()
+                .abs_diff_eq(rgb.to_vec4(), 1e-5));
+            assert_relative_eq!(color.alpha, expected_alpha, epsilon = 1e-5);
+            step(&mut app, 0.25);
+        }
+    }
+
+    #[test]
+    fn emitter_spin_rotates_emission_directions() {
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 10.0.into(),
+                emitter_shape: CircleSegment {
+                    radius: 0.0.into(),
+                    opening_angle: 0.0,
+                    direction_angle: FRAC_PI_2,
+                }
+                .into(),
+                emitter_spin: Some((0.0..PI).into()),
+                system_duration_seconds: 1.0,
+                lifetime: 10.0.into(),
+                ..ParticleSystem::default()
+            },
+        );
+
+        for elapsed in [0.25, 0.5, 0.75] {
+            step(&mut app, 0.25);
+            // The base direction points up, and the spin turns it by half a turn over the duration.
+            let expected = FRAC_PI_2 + PI * elapsed;
+            let expected = Vec3::new(expected.cos(), expected.sin(), 0.0);
+
+            let mut query = app.world_mut().query::<(&Lifetime, &Velocity)>();
+            let spawned: Vec<Vec3> = query
+                .iter(app.world())
+                .filter(|(lifetime, _)| lifetime.0 < f32::EPSILON)
+                .map(|(_, velocity)| velocity.0)
+                .collect();
+            assert!(!spawned.is_empty());
+            for velocity in spawned {
+                assert!(velocity.normalize().abs_diff_eq(expected, 1e-4));
+            }
+        }
+    }
+
+    #[test]
+    fn simulation_speed_scales_movement_only() {
+        let mut app = test_app();
+        let slow = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 10.0.into(),
+                initial_speed: 10.0.into(),
+                lifetime: 10.0.into(),
+                simulation_speed: 0.5,
+                ..ParticleSystem::default()
+            },
+        );
+        let frozen = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 10.0.into(),
+                initial_speed: 10.0.into(),
+                lifetime: 10.0.into(),
+                simulation_speed: 0.0,
+                ..ParticleSystem::default()
+            },
+        );
+        step(&mut app, 0.1);
+
+        let snapshot = |app: &mut App| -> EntityHashMap<(Entity, Vec3, f32)> {
+            app.world_mut()
+                .query::<(Entity, &Particle, &Transform, &Lifetime)>()
+                .iter(app.world())
+                .map(|(entity, particle, transform, lifetime)| {
+                    (
+                        entity,
+                        (particle.parent_system, transform.translation, lifetime.0),
+                    )
+                })
+                .collect()
+        };
+        let before = snapshot(&mut app);
+        assert!(!before.is_empty());
+        step(&mut app, 0.1);
+        let after = snapshot(&mut app);
+
+        for (entity, (system, translation, lifetime)) in before {
+            let (_, moved_to, aged_to) = after[&entity];
+            let expected_distance = if system == slow {
+                0.5
+            } else {
+                assert_eq!(system, frozen);
+                0.0
+            };
+            assert_relative_eq!(
+                translation.distance(moved_to),
+                expected_distance,
+                epsilon = 1e-4
+            );
+            // Particles keep aging at the usual pace.
+            assert_relative_eq!(aged_to - lifetime, 0.1, epsilon = 1e-5);
+        }
+    }
+
+    #[test]
+    fn wind_carries_only_affected_particles() {
+        let mut app = test_app();
+        let still_system = ParticleSystem {
+            spawn_rate_per_second: 0.0.into(),
+            bursts: vec![ParticleBurst::new(0.0, 1)],
+            initial_speed: 0.0.into(),
+            ..ParticleSystem::default()
+        };
+        let blown = spawn_system(&mut app, still_system.clone());
+        app.world_mut()
+            .entity_mut(blown)
+            .insert(AffectedByWind(2.0));
+        let sheltered = spawn_system(&mut app, still_system);
+
+        step(&mut app, 0.1);
+        app.insert_resource(ParticleWind {
+            velocity: Vec3::new(10.0, 0.0, 0.0),
+            turbulence: 0.0,
+        });
+        step(&mut app, 0.1);
+
+        let mut positions = app.world_mut().query::<(&Particle, &Transform)>();
+        for (particle, transform) in positions.iter(app.world()) {
+            if particle.parent_system == blown {
+                assert_relative_eq!(transform.translation.x, 2.0, epsilon = 1e-4);
+            } else {
+                assert_eq!(particle.parent_system, sheltered);
+                assert_eq!(transform.translation, Vec3::ZERO);
+            }
+        }
+        assert_eq!(positions.iter(app.world()).count(), 2);
+    }
+
+    #[test]
+    fn interpolated_spawns_spread_along_emitter_path() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 0.0.into(),
+                bursts: vec![ParticleBurst::new(0.15, 10)],
+                initial_speed: 0.0.into(),
+                interpolate_spawn_positions: true,
+                ..ParticleSystem::default()
+            },
+        );
+
+        step(&mut app, 0.1);
+        app.world_mut()
+            .entity_mut(system)
+            .insert(Transform::from_xyz(100.0, 0.0, 0.0));
+        step(&mut app, 0.1);
+
+        let mut positions: Vec<f32> = app
+            .world_mut()
+            .query_filtered::<&Transform, With<Particle>>()
+            .iter(app.world())
+            .map(|transform| transform.translation.x)
+            .collect();
+        positions.sort_by(f32::total_cmp);
+        assert_eq!(positions.len(), 10);
+        for (x, expected) in positions
+            .into_iter()
+            .zip((1..=10u8).map(|step| f32::from(step) * 10.0))
+        {
+            assert_relative_eq!(x, expected, epsilon = 1e-3);
+        }
+    }
+
+    #[test]
+    fn max_delta_time_caps_spawns_and_movement() {
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 100.0.into(),
+                lifetime: 10.0.into(),
+                initial_speed: 100.0.into(),
+                initial_velocity_override: Some(VectorOverTime::Constant(Vec3::X)),
+                max_delta_time: Some(0.05),
+                ..ParticleSystem::default()
+            },
+        );
+
+        step(&mut app, 0.05);
+        let mut query = app.world_mut().query::<(Entity, &Transform, &Lifetime)>();
+        let (first, transform, _) = query
+            .iter(app.world())
+            .find(|(_, _, lifetime)| lifetime.0 < f32::EPSILON)
+            .unwrap();
+        let start = transform.translation;
+        let spawned = particle_count(&mut app);
+
+        step(&mut app, 0.5);
+        assert_eq!(particle_count(&mut app) - spawned, 5);
+        let transform = app.world().get::<Transform>(first).unwrap();
+        assert_relative_eq!(transform.translation.x - start.x, 5.0, epsilon = 1e-3);
+        assert_relative_eq!(
+            app.world().get::<Lifetime>(first).unwrap().0,
+            0.05,
+            epsilon = 1e-5
+        );
+    }
+
+    #[test]
+    fn stats_list_every_system() {
+        let mut app = test_app();
+        app.init_resource::<ParticleSystemStats>();
+        let looping = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 0.0.into(),
+                bursts: vec![ParticleBurst::new(0.0, 3)],
+                system_duration_seconds: 2.0,
+                ..ParticleSystem::default()
+            },
+        );
+        let oneshot = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 0.0.into(),
+                bursts: vec![ParticleBurst::new(0.0, 5)],
+                system_duration_seconds: 4.0,
+                ..ParticleSystem::oneshot()
+            },
+        );
+        let stopped = app.world_mut().spawn(ParticleSystemBundle::default()).id();
+
+        step(&mut app, 1.0);
+        let stats = app.world().resource::<ParticleSystemStats>();
+        assert_eq!(stats.systems.len(), 3);
+        let stat = |entity| {
+            *stats
+                .systems
+                .iter()
+                .find(|stat| stat.entity == entity)
+                .unwrap()
+        };
+        assert_eq!(
+            stat(looping),
+            ParticleSystemStat {
+                entity: looping,
+                count: 3,
+                playing: true,
+                looping: true,
+                progress: 0.5,
+            }
+        );
+        assert_eq!(
+            stat(oneshot),
+            ParticleSystemStat {
+                entity: oneshot,
+                count: 5,
+                playing: true,
+                looping: false,
+                progress: 0.25,
+            }
+        );
+        assert_eq!(
+            stat(stopped),
+            ParticleSystemStat {
+                entity: stopped,
+                count: 0,
+                playing: false,
+                looping: true,
+                progress: 0.0,
+            }
+        );
+    }
+
+    #[test]
+    fn negative_axis_scale_mirrors_particles() {
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 1.0.into(),
+                initial_scale: 2.0.into(),
+                scale: (1.0..0.5).into(),
+                axis_scale: Vec3::new(-1.0, 1.0, 1.0),
+                ..ParticleSystem::default()
+            },
+        );
+
+        step(&mut app, 0.1);
+        let scale = |app: &mut App| {
+            app.world_mut()
+                .query_filtered::<&Transform, With<Particle>>()
+                .single(app.world())
+                .scale
+        };
+        assert_eq!(scale(&mut app), Vec3::new(-2.0, 2.0, 2.0));
+
+        step(&mut app, 0.25);
+        let scale = scale(&mut app);
+        assert!(scale.x < 0.0);
+        assert_relative_eq!(scale.x, -scale.y);
+    }
+
+    #[test]
+    fn templates_spawn_copies_by_name() {
+        let mut app = test_app();
+        let template = populated_particle_system();
+        app.world_mut()
+            .resource_mut::<ParticleTemplates>()
+            .insert("sparks", template.clone());
+        let transform = Transform::from_xyz(5.0, -3.0, 1.0);
+
+        let spawn = |app: &mut App, name| {
+            app.world_mut()
+                .resource_scope(|world, templates: Mut<ParticleTemplates>| {
+                    let spawned = templates.spawn(&mut world.commands(), name, transform);
+                    world.flush();
+                    spawned
+                })
+        };
+        assert_eq!(spawn(&mut app, "missing"), None);
+        let system = spawn(&mut app, "sparks").unwrap();
+
+        let systems = app
+            .world_mut()
+            .query::<&ParticleSystem>()
+            .iter(app.world())
+            .count();
+        assert_eq!(systems, 1);
+        let world = app.world();
+        assert_eq!(
+            format!("{:?}", world.get::<ParticleSystem>(system).unwrap()),
+            format!("{template:?}")
+        );
+        assert_eq!(*world.get::<Transform>(system).unwrap(), transform);
+        assert!(world.get::<Playing>(system).is_some());
+    }
+
+    #[test]
+    fn speed_driven_color_follows_speed() {
+        let mut app = test_app();
+        let system = |speed: f32| ParticleSystem {
+            spawn_rate_per_second: 1.0.into(),
+            lifetime: 10.0.into(),
+            initial_speed: speed.into(),
+            initial_velocity_override: Some(VectorOverTime::Constant(Vec3::X)),
+            color: ColorOverTime::Lerp(Lerp::new(Color::BLACK, Color::WHITE)),
+            color_driver: ColorDriver::Speed { reference: 100.0 },
+            ..ParticleSystem::default()
+        };
+        let fast = spawn_system(&mut app, system(150.0));
+        let resting = spawn_system(&mut app, system(0.0));
+        let halfway = spawn_system(&mut app, system(50.0));
+
+        step(&mut app, 0.0);
+        step(&mut app, 0.5);
+        let colors: EntityHashMap<LinearRgba> = app
+            .world_mut()
+            .query::<(&Particle, &Sprite)>()
+            .iter(app.world())
+            .map(|(particle, sprite)| (particle.parent_system, sprite.color.to_linear()))
+            .collect();
+        assert_eq!(colors[&fast], LinearRgba::WHITE);
+        assert_eq!(colors[&resting], LinearRgba::BLACK);
+        let expected = ColorOverTime::Lerp(Lerp::new(Color::BLACK, Color::WHITE))
+            .at_lifetime_pct(0.5)
+            .to_linear();
+        assert_relative_eq!(colors[&halfway].red, expected.red, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn world_anchored_particles_emit_along_emitter_and_despawn_with_it() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 4.0.into(),
+                lifetime: 10.0.into(),
+                initial_speed: 10.0.into(),
+                emitter_shape: EmitterShape::CircleSegment(CircleSegment {
+                    opening_angle: 0.0,
+                    ..CircleSegment::default()
+                }),
+                space: ParticleSpace::WorldAnchored,
+                ..ParticleSystem::default()
+            },
+        );
+        let rotate = |app: &mut App, angle| {
+            app.world_mut()
+                .entity_mut(system)
+                .insert(Transform::from_rotation(Quat::from_rotation_z(angle)));
+        };
+
+        rotate(&mut app, FRAC_PI_2);
+        step(&mut app, 0.0);
+        let mut particles = app.world_mut().query::<(Entity, &Velocity, &Transform)>();
+        let (first, velocity, _) = particles.single(app.world());
+        assert!(velocity.0.abs_diff_eq(Vec3::new(0.0, 10.0, 0.0), 1e-4));
+
+        rotate(&mut app, PI);
+        step(&mut app, 0.25);
+        for (entity, velocity, transform) in particles.iter(app.world()) {
+            if entity == first {
+                // Already spawned particles keep simulating in world space.
+                assert!(velocity.0.abs_diff_eq(Vec3::new(0.0, 10.0, 0.0), 1e-4));
+                assert!(transform
+                    .translation
+                    .abs_diff_eq(Vec3::new(0.0, 2.5, 0.0), 1e-4));
+            } else {
+                assert!(velocity.0.abs_diff_eq(Vec3::new(-10.0, 0.0, 0.0), 1e-4));
+            }
+        }
+        assert_eq!(particle_count(&mut app), 2);
+
+        app.world_mut().entity_mut(system).despawn();
+        step(&mut app, 0.1);
+        assert_eq!(particle_count(&mut app), 0);
+    }
+
+    #[test]
+    fn lighter_particles_accelerate_more() {
+        let mut app = test_app();
+        app.insert_resource(GlobalParticleGravity(Vec3::new(-10.0, 0.0, 0.0)));
+        let system = |mass: f32| ParticleSystem {
+            spawn_rate_per_second: 1.0.into(),
+            lifetime: 10.0.into(),
+            initial_speed: 0.0.into(),
+            velocity_modifiers: vec![
+                VelocityModifier::Vector(VectorOverTime::Constant(Vec3::new(0.0, 100.0, 0.0))),
+                VelocityModifier::GlobalGravity(1.0),
+            ],
+            mass: mass.into(),
+            ..ParticleSystem::default()
+        };
+        let light = spawn_system(&mut app, system(0.5));
+        let heavy = spawn_system(&mut app, system(4.0));
+        let weightless = spawn_system(&mut app, system(0.0));
+
+        step(&mut app, 0.0);
+        step(&mut app, 0.1);
+        let velocities: EntityHashMap<Vec3> = app
+            .world_mut()
+            .query::<(&Particle, &Velocity)>()
+            .iter(app.world())
+            .map(|(particle, velocity)| (particle.parent_system, velocity.0))
+            .collect();
+        assert_relative_eq!(velocities[&light].y, 20.0, epsilon = 1e-3);
+        assert_relative_eq!(velocities[&heavy].y, 2.5, epsilon = 1e-3);
+        // Gravity accelerates every mass alike.
+        assert_relative_eq!(velocities[&light].x, -1.0, epsilon = 1e-3);
+        assert_relative_eq!(velocities[&heavy].x, -1.0, epsilon = 1e-3);
+        assert!(velocities[&weightless].is_finite());
+    }
+
+    #[test]
+    fn lifetime_events_fire_once_per_threshold() {
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 0.0.into(),
+                bursts: vec![ParticleBurst::new(0.0, 1)],
+                lifetime: 1.0.into(),
+                lifetime_events: vec![0.5, 0.25, 0.75],
+                ..ParticleSystem::default()
+            },
+        );
+        let mut reader = app
+            .world()
+            .resource::<Events<ParticleLifetimeThreshold>>()
+            .get_reader();
+        let mut read = |app: &mut App| -> Vec<f32> {
+            let events = app.world().resource::<Events<ParticleLifetimeThreshold>>();
+            reader.read(events).map(|event| event.threshold).collect()
+        };
+
+        step(&mut app, 0.0);
+        assert!(read(&mut app).is_empty());
+
+        step(&mut app, 0.3);
+        assert_eq!(read(&mut app), vec![0.25]);
+
+        // A long frame crosses both remaining thresholds at once.
+        step(&mut app, 0.5);
+        assert_eq!(read(&mut app), vec![0.5, 0.75]);
+
+        step(&mut app, 0.1);
+        assert!(read(&mut app).is_empty());
+    }
+
+    #[test]
+    fn finite_loop_count_loops_then_finishes() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 0.0.into(),
+                bursts: vec![ParticleBurst::new(0.0, 1)],
+                lifetime: 0.1.into(),
+                system_duration_seconds: 1.0,
+                loop_count: LoopCount::Finite(3),
+                despawn_on_finish: true,
+                ..ParticleSystem::default()
+            },
+        );
+
+        // Each loop fires the burst at its start, and ends long after the particle died.
+        let mut bursts = 0;
+        for _ in 0..40 {
+            step(&mut app, 0.1);
+            if app.world().get_entity(system).is_none() {
+                break;
+            }
+            bursts += app
+                .world_mut()
+                .query::<&Lifetime>()
+                .iter(app.world())
+                .filter(|lifetime| lifetime.0 < f32::EPSILON)
+                .count();
+        }
+        assert_eq!(bursts, 3);
+        assert!(app.world().get_entity(system).is_none());
+    }
+
+    #[test]
+    fn group_limits_despawn_oldest_systems() {
+        const FOOTSTEPS: GroupId = GroupId(3);
+        let mut app = test_app();
+        app.insert_resource(GroupLimits::default().with_limit(FOOTSTEPS, 2));
+
+        let mut systems = Vec::new();
+        for _ in 0..4 {
+            let system = spawn_system(
+                &mut app,
+                ParticleSystem {
+                    spawn_rate_per_second: 10.0.into(),
+                    lifetime: 100.0.into(),
+                    despawn_particles_with_system: true,
+                    ..ParticleSystem::default()
+                },
+            );
+            app.world_mut()
+                .entity_mut(system)
+                .insert(ParticleSystemGroup(FOOTSTEPS));
+            systems.push(system);
+            step(&mut app, 0.25);
+        }
+        // An ungrouped system is never limited.
+        let ungrouped = spawn_system(&mut app, ParticleSystem::default());
+        step(&mut app, 0.25);
+        step(&mut app, 0.25);
+
+        assert!(app.world().get_entity(systems[0]).is_none());
+        assert!(app.world().get_entity(systems[1]).is_none());
+        assert!(app.world().get_entity(systems[2]).is_some());
+        assert!(app.world().get_entity(systems[3]).is_some());
+        assert!(app.world().get_entity(ungrouped).is_some());
+
+        // Particles of the despawned systems were despawned along with them.
+        let mut query = app.world_mut().query::<&Particle>();
+        assert!(query
+            .iter(app.world())
+            .all(|particle| particle.parent_system != systems[0]
+                && particle.parent_system != systems[1]));
+    }
+
+    #[test]
+    fn group_limits_follow_systems_moved_between_groups() {
+        const SPARKS: GroupId = GroupId(1);
+        const SMOKE: GroupId = GroupId(2);
+        let mut app = test_app();
+        app.insert_resource(GroupLimits::default().with_limit(SMOKE, 1));
+
+        let smoke = spawn_system(&mut app, ParticleSystem::default());
+        app.world_mut()
+            .entity_mut(smoke)
+            .insert(ParticleSystemGroup(SMOKE));
+        let moved = spawn_system(
+            &mut app,
+            ParticleSystem {
+                space: ParticleSpace::Local,
+                lifetime: 100.0.into(),
+                ..ParticleSystem::default()
+            },
+        );
+        app.world_mut()
+            .entity_mut(moved)
+            .insert(ParticleSystemGroup(SPARKS));
+        step(&mut app, 0.25);
+        assert!(app.world().get_entity(smoke).is_some());
+        assert!(particle_count(&mut app) > 0);
+
+        // Joining the limited group despawns its oldest member, then the moved system once it is the oldest.
+        app.world_mut()
+            .get_mut::<ParticleSystemGroup>(moved)
+            .unwrap()
+            .0 = SMOKE;
+        step(&mut app, 0.25);
+        assert!(app.world().get_entity(smoke).is_none());
+        assert!(app.world().get_entity(moved).is_some());
+
+        let newest = spawn_system(&mut app, ParticleSystem::default());
+        app.world_mut()
+            .entity_mut(newest)
+            .insert(ParticleSystemGroup(SMOKE));
+        step(&mut app, 0.25);
+        assert!(app.world().get_entity(moved).is_none());
+        // The local particles parented to the moved system were despawned along with it.
+        let mut query = app.world_mut().query::<&Particle>();
+        assert!(query
+            .iter(app.world())
+            .all(|particle| particle.parent_system != moved));
+    }
+
+    #[test]
+    fn impulse_pushes_live_particles() {
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 20.0.into(),
+                initial_speed: 0.0.into(),
+                lifetime: 100.0.into(),
+                ..ParticleSystem::default()
+            },
+        );
+        step(&mut app, 0.25);
+
+        let mut query = app
+            .world_mut()
+            .query::<(&mut Velocity, &Transform, &Particle)>();
+        let mut start_positions = Vec::new();
+        for (mut velocity, transform, _) in query.iter_mut(app.world_mut()) {
+            velocity.add_impulse(Vec3::new(0.0, 10.0, 0.0));
+            start_positions.push(transform.translation);
+        }
+        assert!(!start_positions.is_empty());
+        step(&mut app, 0.1);
+
+        for ((velocity, transform, _), start) in query.iter(app.world()).zip(start_positions) {
+            assert_relative_eq!(velocity.speed(), 10.0, epsilon = 1e-4);
+            assert!(velocity.direction().abs_diff_eq(Vec3::Y, 1e-4));
+            assert!(transform.translation.y > start.y);
+        }
+    }
+
+    #[test]
+    fn random_frame_offset_desyncs_animations() {
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                texture: ParticleTexture::TextureAtlas {
+                    texture: Handle::default(),
+                    atlas: Handle::default(),
+                    index: AtlasIndex::Animated(
+                        AnimatedIndex::from_fps((0..8).collect(), 10.0).with_random_frame_offset(),
+                    ),
+                },
+                spawn_rate_per_second: 0.0.into(),
+                bursts: vec![ParticleBurst::new(0.0, 20)],
+                lifetime: 100.0.into(),
+                seed: Some(7),
+                ..ParticleSystem::default()
+            },
+        );
+
+        step(&mut app, 0.1);
+        step(&mut app, 0.1);
+
+        let mut query = app
+            .world_mut()
+            .query_filtered::<(&TextureAtlas, &Lifetime), With<Particle>>();
+        let particles: Vec<_> = query
+            .iter(app.world())
+            .map(|(atlas, lifetime)| (atlas.index, lifetime.0))
+            .collect();
+        assert_eq!(particles.len(), 20);
+        assert!(particles
+            .iter()
+            .all(|(_, lifetime)| (lifetime - particles[0].1).abs() < 1e-6));
+        assert!(particles.iter().any(|(index, _)| *index != particles[0].0));
+    }
+
+    #[test]
+    fn animated_lerp_palette_follows_system_progress() {
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 4.0.into(),
+                lifetime: 100.0.into(),
+                system_duration_seconds: 1.0,
+                looping: false,
+                color: ColorOverTime::AnimatedLerp {
+                    a: Curve::new(vec![
+                        CurvePoint::new(Color::linear_rgb(1.0, 0.0, 0.0), 0.0),
+                        CurvePoint::new(Color::linear_rgb(0.0, 0.0, 1.0), 1.0),
+                    ]),
+                    b: Curve::new(vec![
+                        CurvePoint::new(Color::WHITE, 0.0),
+                        CurvePoint::new(Color::WHITE, 1.0),
+                    ]),
+                },
+                ..ParticleSystem::default()
+            },
+        );
+
+        for _ in 0..4 {
+            step(&mut app, 0.25);
+        }
+
+        let mut query = app.world_mut().query::<(&Particle, &Sprite)>();
+        let mut particles: Vec<_> = query
+            .iter(app.world())
+            .map(|(particle, sprite)| (particle.system_pct, sprite.color.to_linear()))
+            .collect();
+        particles.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let (early_pct, early) = particles[0];
+        let (late_pct, late) = particles[particles.len() - 1];
+        assert!(early_pct < late_pct);
+        assert!(early.red > late.red);
+        assert!(early.blue < late.blue);
+    }
+
+    #[test]
+    fn shockwave_spawns_one_growing_particle() {
+        let shockwave = ParticleSystem::shockwave(Handle::default(), 50.0, 1.0);
+        assert!(!shockwave.looping);
+        assert_eq!(shockwave.max_particles, 1);
+        assert_relative_eq!(shockwave.scale.at_lifetime_pct(0.0), 0.0);
+        assert_relative_eq!(shockwave.scale.at_lifetime_pct(1.0), 50.0);
+
+        let mut app = test_app();
+        let system = spawn_system(&mut app, shockwave);
+        for _ in 0..3 {
+            step(&mut app, 0.25);
+        }
+        assert_eq!(particle_count(&mut app), 1);
+
+        let mut query = app.world_mut().query::<(&Particle, &Transform, &Sprite)>();
+        let (_, transform, sprite) = query.single(app.world());
+        assert!(transform.scale.x > 0.0 && transform.scale.x < 50.0);
+        assert!(sprite.color.alpha() < 1.0);
+
+        for _ in 0..3 {
+            step(&mut app, 0.25);
+        }
+        assert_eq!(particle_count(&mut app), 0);
+        assert!(app.world().get_entity(system).is_none());
+    }
+
+    #[test]
+    fn burst_requests_in_one_frame_add_up() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 0.0.into(),
+                lifetime: 100.0.into(),
+                ..ParticleSystem::default()
+            },
+        );
+        step(&mut app, 0.1);
+        assert_eq!(particle_count(&mut app), 0);
+
+        for count in [5, 10, 15] {
+            app.world_mut().send_event(ParticleBurstRequest {
+                entity: system,
+                count,
+            });
+        }
+        step(&mut app, 0.1);
+        assert_eq!(particle_count(&mut app), 30);
+
+        step(&mut app, 0.1);
+        assert_eq!(particle_count(&mut app), 30);
+    }
+
+    #[test]
+    fn burst_requests_while_paused_are_dropped() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 0.0.into(),
+                lifetime: 100.0.into(),
+                ..ParticleSystem::default()
+            },
+        );
+        step(&mut app, 0.1);
+        app.world_mut().entity_mut(system).remove::<Playing>();
+
+        app.world_mut().send_event(ParticleBurstRequest {
+            entity: system,
+            count: 10,
+        });
+        step(&mut app, 0.1);
+        assert_eq!(particle_count(&mut app), 0);
+        assert_eq!(
+            app.world()
+                .get::<RunningState>(system)
+                .unwrap()
+                .requested_particles,
+            0
+        );
+
+        app.world_mut().entity_mut(system).insert(Playing);
+        step(&mut app, 0.1);
+        assert_eq!(particle_count(&mut app), 0);
+    }
+
+    #[test]
+    fn min_particles_refills_killed_particles() {
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 0.0.into(),
+                min_particles: 10,
+                lifetime: 100.0.into(),
+                ..ParticleSystem::default()
+            },
+        );
+        step(&mut app, 0.1);
+        assert_eq!(particle_count(&mut app), 10);
+
+        let mut query = app.world_mut().query::<(&Particle, &mut Lifetime)>();
+        for (_, mut lifetime) in query.iter_mut(app.world_mut()).take(4) {
+            lifetime.0 = 1000.0;
+        }
+        step(&mut app, 0.1);
+        assert_eq!(particle_count(&mut app), 10);
+    }
+
+    #[test]
+    fn debris_outlive_their_lifetime() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 0.0.into(),
+                bursts: vec![ParticleBurst::new(0.0, 5)],
+                lifetime: 0.5.into(),
+                system_duration_seconds: 1.0,
+                looping: false,
+                ..ParticleSystem::default()
+            },
+        );
+        app.world_mut().entity_mut(system).insert(DebrisMode);
+
+        for _ in 0..20 {
+            step(&mut app, 0.25);
+        }
+
+        let mut query = app.world_mut().query_filtered::<&Particle, With<Debris>>();
+        assert_eq!(query.iter(app.world()).count(), 5);
+        assert_eq!(particle_count(&mut app), 5);
+        assert!(app.world().get::<Playing>(system).is_some());
+    }
+
+    #[test]
+    fn density_alpha_fades_dense_clusters() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 0.0.into(),
+                bursts: vec![ParticleBurst::new(0.0, 16), ParticleBurst::new(0.5, 1)],
+                emitter_shape: EmitterShape::circle(0.0),
+                initial_speed: 0.0.into(),
+                lifetime: 100.0.into(),
+                ..ParticleSystem::default()
+            },
+        );
+        app.world_mut()
+            .entity_mut(system)
+            .insert(DensityAlpha::new(10.0, 1.0));
+
+        step(&mut app, 0.25);
+        // The last particle bursts far from the cluster.
+        app.world_mut()
+            .entity_mut(system)
+            .insert(Transform::from_xyz(500.0, 0.0, 0.0));
+        for _ in 0..3 {
+            step(&mut app, 0.25);
+        }
+
+        let mut query = app.world_mut().query::<(&Particle, &Transform, &Sprite)>();
+        let (dense, sparse): (Vec<_>, Vec<_>) = query
+            .iter(app.world())
+            .partition(|(_, transform, _)| transform.translation.x < 250.0);
+        assert_eq!(dense.len(), 16);
+        assert_eq!(sparse.len(), 1);
+        for (_, _, sprite) in dense {
+            assert_relative_eq!(sprite.color.alpha(), 0.25, epsilon = 1e-4);
+        }
+        assert_relative_eq!(sparse[0].2.color.alpha(), 1.0);
+    }
+
+    #[test]
+    fn seek_to_moves_playhead_and_skips_passed_bursts() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 0.0.into(),
+                bursts: vec![
+                    ParticleBurst::new(0.0, 1),
+                    ParticleBurst::new(1.0, 2),
+                    ParticleBurst::new(2.0, 4),
+                    ParticleBurst::new(3.0, 8),
+                ],
+                lifetime: 100.0.into(),
+                system_duration_seconds: 5.0,
+                ..ParticleSystem::default()
+            },
+        );
+        step(&mut app, 0.1);
+        assert_eq!(particle_count(&mut app), 1);
+
+        app.world_mut().entity_mut(system).insert(SeekTo(2.5));
+        step(&mut app, 0.1);
+        let running_state = app.world().get::<RunningState>(system).unwrap();
+        assert_relative_eq!(running_state.running_time, 2.6, epsilon = 1e-4);
+        assert_eq!(app.world().get::<BurstIndex>(system).unwrap().0, 3);
+        assert!(app.world().get::<SeekTo>(system).is_none());
+        // The bursts at 1.0 and 2.0 were skipped.
+        assert_eq!(particle_count(&mut app), 1);
+
+        for _ in 0..5 {
+            step(&mut app, 0.1);
+        }
+        assert_eq!(particle_count(&mut app), 9);
+
+        // Seeking backwards clears the particles and replays the bursts.
+        app.world_mut().entity_mut(system).insert(SeekTo(0.0));
+        step(&mut app, 0.1);
+        assert_eq!(particle_count(&mut app), 1);
+        assert_eq!(app.world().get::<ParticleCount>(system).unwrap().0, 1);
+    }
+
+    #[test]
+    fn seeking_back_re_emits_capped_systems() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 100.0.into(),
+                lifetime: 0.5.into(),
+                system_duration_seconds: 5.0,
+                total_particles_to_emit: Some(5),
+                ..ParticleSystem::default()
+            },
+        );
+        for _ in 0..10 {
+            step(&mut app, 0.1);
+        }
+        assert_eq!(
+            app.world()
+                .get::<RunningState>(system)
+                .unwrap()
+                .total_spawned,
+            5
+        );
+        assert_eq!(particle_count(&mut app), 0);
+
+        app.world_mut()
+            .entity_mut(system)
+            .insert((SeekTo(0.0), Playing));
+        step(&mut app, 0.1);
+        assert!(particle_count(&mut app) > 0);
+    }
+
+    #[test]
+    fn flicker_varies_alpha_within_bounds() {
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 1.0.into(),
+                lifetime: 10.0.into(),
+                initial_speed: 0.0.into(),
+                flicker: Some(FlickerModifier::new(30.0, 0.5)),
+                ..ParticleSystem::default()
+            },
+        );
+
+        let mut alphas = Vec::new();
+        for _ in 0..30 {
+            step(&mut app, 0.05);
+            let mut query = app.world_mut().query::<(&Particle, &Sprite)>();
+            if let Some((_, sprite)) = query.iter(app.world()).next() {
+                alphas.push(sprite.color.alpha());
+            }
+        }
+
+        assert!(alphas.len() > 20);
+        assert!(alphas.iter().all(|alpha| (0.5..=1.0).contains(alpha)));
+        assert!(alphas
+            .windows(2)
+            .any(|pair| (pair[0] - pair[1]).abs() > 0.01));
+    }
+
+    #[test]
+    fn min_particles_warning_is_tracked_per_system() {
+        let mut app = test_app();
+        let misconfigured = |app: &mut App| {
+            spawn_system(
+                app,
+                ParticleSystem {
+                    min_particles: 20,
+                    max_particles: 10,
+                    ..ParticleSystem::default()
+                },
+            )
+        };
+        let first = misconfigured(&mut app);
+        let second = misconfigured(&mut app);
+        let valid = spawn_system(&mut app, ParticleSystem::default());
+
+        step(&mut app, 0.1);
+
+        let warned = |app: &App, entity: Entity| {
+            app.world()
+                .get::<RunningState>(entity)
+                .unwrap()
+                .has_warned(SystemWarning::MinParticles)
+        };
+        assert!(warned(&app, first));
+        assert!(warned(&app, second));
+        assert!(!warned(&app, valid));
+    }
+
+    #[test]
+    fn zero_axis_scale_warning_is_tracked_per_system() {
+        let mut app = test_app();
+        let flat = |app: &mut App, axis_scale| {
+            spawn_system(
+                app,
+                ParticleSystem {
+                    axis_scale,
+                    ..ParticleSystem::default()
+                },
+            )
+        };
+        let flat_x = flat(&mut app, Vec3::new(0.0, 1.0, 1.0));
+        let flat_y = flat(&mut app, Vec3::new(1.0, 0.0, 1.0));
+        let visible = flat(&mut app, Vec3::ONE);
+
+        step(&mut app, 0.1);
+
+        let warned = |app: &App, entity: Entity| {
+            app.world()
+                .get::<RunningState>(entity)
+                .unwrap()
+                .has_warned(SystemWarning::ZeroAxisScale)
+        };
+        assert!(warned(&app, flat_x));
+        assert!(warned(&app, flat_y));
+        assert!(!warned(&app, visible));
+    }
+
+    #[test]
+    fn missing_texture_warning_is_tracked_per_system() {
+        let mut app = test_app();
+        let untextured = spawn_system(&mut app, ParticleSystem::default());
+        let textured = spawn_system(
+            &mut app,
+            ParticleSystem {
+                texture: Handle::<Image>::weak_from_u128(42).into(),
+                ..ParticleSystem::default()
+            },
+        );
+
+        let warned = |app: &App, entity: Entity| {
+            app.world()
+                .get::<RunningState>(entity)
+                .unwrap()
+                .has_warned(SystemWarning::MissingTexture)
+        };
+
+        step(&mut app, 0.1);
+        assert!(warned(&app, untextured));
+        assert!(!warned(&app, textured));
+
+        step(&mut app, 0.1);
+        assert!(warned(&app, untextured));
+        assert!(!warned(&app, textured));
+
+        // Restarting the system doesn't log its warnings again.
+        app.world_mut().entity_mut(untextured).insert(EmitOnce);
+        step(&mut app, 0.1);
+        assert!(warned(&app, untextured));
+    }
+
+    #[test]
+    fn initial_scale_multiplies_scale_curve_without_changing_its_shape() {
+        let scale = ValueOverTime::from(vec![
+            CurvePoint::new(0.0, 0.0),
+            CurvePoint::new(1.0, 0.1),
+            CurvePoint::new(0.0, 1.0),
+        ]);
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 0.0.into(),
+                lifetime: 1.0.into(),
+                initial_speed: 0.0.into(),
+                initial_scale: JitteredValue::jittered(1.0, -0.5..1.5),
+                scale: scale.clone(),
+                bursts: vec![ParticleBurst::new(0.0, 20)],
+                ..ParticleSystem::default()
+            },
+        );
+
+        for _ in 0..15 {
+            step(&mut app, 0.05);
+            let mut query = app
+                .world_mut()
+                .query::<(&Particle, &Lifetime, &Transform)>();
+            let shapes: Vec<(f32, f32)> = query
+                .iter(app.world())
+                .map(|(particle, lifetime, transform)| {
+                    (
+                        transform.scale.x / particle.initial_scale,
+                        lifetime.0 / particle.max_lifetime,
+                    )
+                })
+                .collect();
+            assert_eq!(shapes.len(), 20);
+            let (shape, pct) = shapes[0];
+            for (other_shape, _) in &shapes {
+                assert_relative_eq!(*other_shape, shape, max_relative = 1e-4);
+            }
+            // The lifetime may be advanced before or after the transform within a frame.
+            let expected = [
+                scale.at_lifetime_pct(pct),
+                scale.at_lifetime_pct(pct - 0.05),
+            ];
+            assert!(expected.iter().any(|value| (value - shape).abs() < 1e-4));
+        }
+    }
+
+    #[test]
+    fn mesh_surface_emitter_waits_for_mesh() {
+        let mut app = test_app();
+        app.insert_resource(Assets::<Mesh>::default());
+        let mesh = app.world_mut().resource_mut::<Assets<Mesh>>().add(
+            Mesh::new(
+                PrimitiveTopology::TriangleList,
+                RenderAssetUsages::default(),
+            )
+            .with_inserted_attribute(
+                Mesh::ATTRIBUTE_POSITION,
+                vec![[0.0, 0.0, 0.0], [4.0, 0.0, 0.0], [0.0, 4.0, 0.0]],
+            ),
+        );
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 20.0.into(),
+                emitter_shape: EmitterShape::MeshSurface(Handle::weak_from_u128(42)),
+                initial_speed: 0.0.into(),
+                ..ParticleSystem::default()
+            },
+        );
+        let loaded = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 20.0.into(),
+                emitter_shape: EmitterShape::MeshSurface(mesh),
+                initial_speed: 0.0.into(),
+                ..ParticleSystem::default()
+            },
+        );
+
+        step(&mut app, 0.5);
+
+        let mut query = app.world_mut().query::<(&Particle, &Transform)>();
+        assert!(query.iter(app.world()).count() > 0);
+        for (particle, transform) in query.iter(app.world()) {
+            assert_eq!(particle.parent_system, loaded);
+            let position = transform.translation;
+            assert!(position.x >= 0.0 && position.y >= 0.0 && position.x + position.y <= 4.0001);
+        }
+    }
+
+    #[test]
+    fn mesh_surface_sampler_is_rebuilt_when_mesh_is_modified() {
+        let mut app = test_app();
+        app.insert_resource(Assets::<Mesh>::default())
+            .add_event::<AssetEvent<Mesh>>();
+        let triangle = |offset: f32| {
+            Mesh::new(
+                PrimitiveTopology::TriangleList,
+                RenderAssetUsages::default(),
+            )
+            .with_inserted_attribute(
+                Mesh::ATTRIBUTE_POSITION,
+                vec![
+                    [offset, 0.0, 0.0],
+                    [offset + 4.0, 0.0, 0.0],
+                    [offset, 4.0, 0.0],
+                ],
+            )
+        };
+        let mesh = app
+            .world_mut()
+            .resource_mut::<Assets<Mesh>>()
+            .add(triangle(0.0));
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 20.0.into(),
+                emitter_shape: EmitterShape::MeshSurface(mesh.clone()),
+                initial_speed: 0.0.into(),
+                ..ParticleSystem::default()
+            },
+        );
+        let positions = |app: &mut App| {
+            let mut query = app
+                .world_mut()
+                .query_filtered::<&Transform, With<Particle>>();
+            query
+                .iter(app.world())
+                .map(|transform| transform.translation.x)
+                .collect::<Vec<_>>()
+        };
+
+        step(&mut app, 0.5);
+        assert!(positions(&mut app).iter().all(|x| *x <= 4.0001));
+
+        // The cached sampler keeps being used until the mesh is reported as modified.
+        app.world_mut()
+            .resource_mut::<Assets<Mesh>>()
+            .insert(&mesh, triangle(100.0));
+        step(&mut app, 0.5);
+        assert!(positions(&mut app).iter().all(|x| *x <= 4.0001));
+
+        app.world_mut()
+            .send_event(AssetEvent::Modified { id: mesh.id() });
+        step(&mut app, 0.5);
+        assert!(positions(&mut app).iter().any(|x| *x >= 100.0));
+    }
+
+    #[test]
+    fn editing_velocity_modifiers_affects_live_particles() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 1.0.into(),
+                lifetime: 10.0.into(),
+                initial_speed: 0.0.into(),
+                ..ParticleSystem::default()
+            },
+        );
+
+        step(&mut app, 0.1);
+        step(&mut app, 0.1);
+        let velocity = |app: &mut App| app.world_mut().query::<&Velocity>().single(app.world()).0;
+        assert_eq!(velocity(&mut app), Vec3::ZERO);
+
+        app.world_mut()
+            .get_mut::<ParticleSystem>(system)
+            .unwrap()
+            .velocity_modifiers
+            .push(VelocityModifier::Vector(Vec3::new(0.0, 10.0, 0.0).into()));
+        step(&mut app, 0.1);
+        assert!(velocity(&mut app).abs_diff_eq(Vec3::new(0.0, 1.0, 0.0), 1e-4));
+
+        // Once the system is gone, particles keep the modifiers they were spawned with.
+        app.world_mut().entity_mut(system).despawn();
+        step(&mut app, 0.1);
+        assert!(velocity(&mut app).abs_diff_eq(Vec3::new(0.0, 1.0, 0.0), 1e-4));
+    }
+
+    #[test]
+    fn editing_velocity_constraint_affects_live_particles() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 1.0.into(),
+                lifetime: 10.0.into(),
+                initial_speed: 0.0.into(),
+                velocity_modifiers: vec![VelocityModifier::Vector(
+                    Vec3::new(10.0, 10.0, 0.0).into(),
+                )],
+                ..ParticleSystem::default()
+            },
+        );
+
+        step(&mut app, 0.1);
+        step(&mut app, 0.1);
+        let velocity = |app: &mut App| app.world_mut().query::<&Velocity>().single(app.world()).0;
+        assert!(velocity(&mut app).y > 0.0);
+
+        app.world_mut()
+            .get_mut::<ParticleSystem>(system)
+            .unwrap()
+            .velocity_constraint = Some(VelocityConstraint {
+            lock_y: true,
+            ..VelocityConstraint::default()
+        });
+        step(&mut app, 0.1);
+        assert!(velocity(&mut app).y.abs() < 1e-6);
+        assert!(velocity(&mut app).x > 0.0);
+    }
+
+    #[test]
+    fn beat_emitter_fires_every_interval() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 0.0.into(),
+                lifetime: 100.0.into(),
+                system_duration_seconds: 0.3,
+                ..ParticleSystem::default()
+            },
+        );
+        app.world_mut()
+            .entity_mut(system)
+            .insert(BeatEmitter::new(0.5, 3).with_offset(0.25));
+
+        // Beats at 0.25, 0.75, 1.25 and 1.75, independent of the 0.3 second looping duration.
+        for _ in 0..20 {
+            step(&mut app, 0.1);
+        }
+        assert_eq!(particle_count(&mut app), 12);
+
+        // Slowing down keeps the scheduled beat at 2.25, then uses the new interval.
+        app.world_mut()
+            .get_mut::<BeatEmitter>(system)
+            .unwrap()
+            .interval = 1.0;
+        for _ in 0..15 {
+            step(&mut app, 0.1);
+        }
+        assert_eq!(particle_count(&mut app), 18);
+    }
+
+    #[test]
+    fn beat_emitter_catches_up_on_tiny_intervals() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 0.0.into(),
+                max_particles: 50,
+                ..ParticleSystem::default()
+            },
+        );
+        // Adding the interval one beat at a time would never catch up, as it is lost in the float precision.
+        app.world_mut()
+            .entity_mut(system)
+            .insert(BeatEmitter::new(1e-9, 1));
+
+        step(&mut app, 1.0);
+        assert_eq!(particle_count(&mut app), 50);
+    }
+
+    #[test]
+    fn speed_by_radius_speeds_up_outer_particles() {
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 200.0.into(),
+                emitter_shape: EmitterShape::circle(JitteredValue::jittered(5.0, -5.0..5.0)),
+                initial_speed: 10.0.into(),
+                speed_by_radius: Some(Curve::new(vec![
+                    CurvePoint::new(1.0, 0.0),
+                    CurvePoint::new(3.0, 1.0),
+                ])),
+                ..ParticleSystem::default()
+            },
+        );
+
+        step(&mut app, 0.5);
+
+        let mut query = app
+            .world_mut()
+            .query::<(&Particle, &DistanceTraveled, &Velocity)>();
+        assert!(query.iter(app.world()).count() > 10);
+        for (_, distance, velocity) in query.iter(app.world()) {
+            let normalized = distance.from.length() / 10.0;
+            assert_relative_eq!(
+                velocity.0.length(),
+                10.0 * (1.0 + 2.0 * normalized),
+                max_relative = 1e-3
+            );
+        }
+    }
+
+    #[test]
+    fn sequential_iteration_matches_parallel() {
+        let simulate = |parallel: bool| {
+            let mut world = World::new();
+            for index in 0..1000_u16 {
+                let index = f32::from(index);
+                world.spawn((
+                    Velocity(Vec3::new(index.sin(), index.cos(), 0.0) * index),
+                    Transform::from_xyz(index, -index, 0.0),
+                ));
+            }
+            world.run_system_once(move |mut query: Query<(&Velocity, &mut Transform)>| {
+                for_each_mut_with(&mut query, parallel, |(velocity, mut transform)| {
+                    transform.translation += velocity.0 * 0.016;
+                    transform.rotate_z(velocity.0.length() * 0.01);
+                });
+            });
+            let mut query = world.query::<(Entity, &Transform)>();
+            let mut results: Vec<(Entity, Transform)> = query
+                .iter(&world)
+                .map(|(entity, transform)| (entity, *transform))
+                .collect();
+            results.sort_by_key(|(entity, _)| *entity);
+            results
+        };
+
+        assert_eq!(simulate(true), simulate(false));
+    }
+
+    #[test]
+    fn fractional_spawn_rates_are_accurate() {
+        let frames: u16 = 1170;
+        let duration = f32::from(frames) / 60.0;
+        for rate in [0.1, 0.5, 2.0, 60.0] {
+            let mut app = test_app();
+            let system = spawn_system(
+                &mut app,
+                ParticleSystem {
+                    max_particles: 10_000,
+                    spawn_rate_per_second: rate.into(),
+                    lifetime: 100.0.into(),
+                    system_duration_seconds: 100.0,
+                    ..ParticleSystem::default()
+                },
+            );
+
+            for _ in 0..frames {
+                step(&mut app, 1.0 / 60.0);
+            }
+
+            let spawned = app
+                .world()
+                .get::<RunningState>(system)
+                .unwrap()
+                .total_spawned;
+            let spawned = f32::from(u16::try_from(spawned).unwrap());
+            assert!(
+                (spawned - rate * duration).abs() <= 1.0,
+                "rate {rate} spawned {spawned} particles"
+            );
+        }
+    }
+
+    #[test]
+    fn world_space_emission_follows_emitter_rotation() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 0.0.into(),
+                emitter_shape: CircleSegment {
+                    opening_angle: 0.0,
+                    ..CircleSegment::default()
+                }
+                .into(),
+                initial_speed: 10.0.into(),
+                lifetime: 10.0.into(),
+                space: ParticleSpace::World,
+                ..ParticleSystem::default()
+            },
+        );
+        app.world_mut().entity_mut(system).insert((
+            Transform::from_rotation(Quat::from_rotation_z(FRAC_PI_2)),
+            BeatEmitter::new(100.0, 1),
+        ));
+
+        step(&mut app, 0.1);
+        let velocity = app.world_mut().query::<&Velocity>().single(app.world()).0;
+        assert!(velocity.abs_diff_eq(Vec3::new(0.0, 10.0, 0.0), 1e-4));
+
+        // Rotating the emitter afterwards doesn't affect particles already in flight.
+        app.world_mut()
+            .get_mut::<Transform>(system)
+            .unwrap()
+            .rotation = Quat::IDENTITY;
+        step(&mut app, 0.1);
+        step(&mut app, 0.1);
+        let mut query = app.world_mut().query::<(&Velocity, &Transform)>();
+        let (velocity, transform) = query.single(app.world());
+        assert!(velocity.0.abs_diff_eq(Vec3::new(0.0, 10.0, 0.0), 1e-4));
+        assert_relative_eq!(transform.translation.x, 0.0, epsilon = 1e-4);
+        assert!(transform.translation.y > 0.0);
+    }
+
+    #[test]
+    fn non_positive_lifetimes_are_clamped() {
+        let mut app = test_app();
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 100.0.into(),
+                lifetime: JitteredValue::jittered(0.1, -1.0..0.0),
+                scale: (1.0..0.0).into(),
+                ..ParticleSystem::default()
+            },
+        );
+
+        for _ in 0..10 {
+            step(&mut app, 0.02);
+            let mut query = app.world_mut().query::<(&Particle, &Sprite, &Transform)>();
+            for (particle, sprite, transform) in query.iter(app.world()) {
+                assert!(particle.max_lifetime > 0.0);
+                assert!(LinearRgba::from(sprite.color).to_vec4().is_finite());
+                assert!(transform.is_finite());
+            }
+        }
+    }
+
+    #[test]
+    fn short_lifetime_warning_is_tracked_per_system() {
+        let mut app = test_app();
+        let short_lived = |lifetime: f32| ParticleSystem {
+            spawn_rate_per_second: 10.0.into(),
+            lifetime: lifetime.into(),
+            ..ParticleSystem::default()
+        };
+        let first = spawn_system(&mut app, short_lived(0.0));
+        let second = spawn_system(&mut app, short_lived(-1.0));
+        let valid = spawn_system(&mut app, short_lived(1.0));
+
+        step(&mut app, 0.1);
+
+        let warned = |entity: Entity| {
+            app.world()
+                .get::<RunningState>(entity)
+                .unwrap()
+                .has_warned(SystemWarning::ShortLifetime)
+        };
+        assert!(warned(first));
+        assert!(warned(second));
+        assert!(!warned(valid));
+    }
+
+    #[test]
+    fn emission_window_repeats_each_loop() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                max_particles: 10_000,
+                spawn_rate_per_second: 10.0.into(),
+                lifetime: 100.0.into(),
+                system_duration_seconds: 5.0,
+                emission_window: Some(1.0..2.0),
+                ..ParticleSystem::default()
+            },
+        );
+
+        let mut previous = 0;
+        for _ in 0..120 {
+            step(&mut app, 0.1);
+            let running_state = app.world().get::<RunningState>(system).unwrap();
+            if running_state.total_spawned > previous {
+                assert!((1.0..2.1).contains(&running_state.running_time));
+                previous = running_state.total_spawned;
+            }
+        }
+        // Three windows, each emitting about 10 particles.
+        assert!(
+            (28..=32).contains(&previous),
+            "spawned {previous} particles"
+        );
+    }
+
+    #[test]
+    fn burst_order_is_resolved_again_when_duration_changes() {
+        let mut app = test_app();
+        let system = spawn_system(
+            &mut app,
+            ParticleSystem {
+                spawn_rate_per_second: 0.0.into(),
+                lifetime: 100.0.into(),
+                system_duration_seconds: 8.0,
+                looping: false,
+                bursts: vec![
+                    ParticleBurst::new(3.0, 1),
+                    ParticleBurst::at_fraction(0.5, 10),
+                ],
+                ..ParticleSystem::default()
+            },
+        );
+        step(&mut app, 1.0);
+        assert_eq!(
+            app.world().get::<RunningState>(system).unwrap().burst_order,
+            vec![(3.0, 1), (4.0, 10)]
+        );
+
+        // Halving the duration moves the relative burst before the absolute one.
+        app.world_mut()
+            .get_mut::<ParticleSystem>(system)
+            .unwrap()
+            .system_duration_seconds = 4.0;
+        step(&mut app, 1.5);
+        assert_eq!(particle_count(&mut app), 10);
+        assert_eq!(
+            app.world().get::<RunningState>(system).unwrap().burst_order,
+            vec![(2.0, 10), (3.0, 1)]
+        );
+    }
+
+    #[test]
+    fn fractional_bursts_follow_system_duration() {
+        let burst_time = |system_duration_seconds: f32| {
+            let mut app = test_app();
+            let system = spawn_system(
+                &mut app,
+                ParticleSystem {
+                    spawn_rate_per_second: 0.0.into(),
+                    lifetime: 100.0.into(),
+                    system_duration_seconds,
+                    looping: false,
+                    bursts: vec![
+                        ParticleBurst::new(0.5, 1),
+                        ParticleBurst::at_fraction(0.5, 10),
+                    ],
+                    ..ParticleSystem::default()
+                },
+            );
+            let mut times = Vec::new();
+            let mut previous = 0;
+            for _ in 0..100 {
+                step(&mut app, 0.1);
+                let count = particle_count(&mut app);
+                if count > previous {
+                    times.push((
+                        app.world()
+                            .get::<RunningState>(system)
+                            .unwrap()
+                            .running_time,
+                        count - previous,
+                    ));
+                    previous = count;
+                }
+            }
+            times
+        };
+
+        let check = |times: Vec<(f32, usize)>, expected: [(f32, usize); 2]| {
+            assert_eq!(times.len(), 2);
+            for ((time, count), (expected_time, expected_count)) in times.into_iter().zip(expected)
+            {
+                assert!((expected_time..expected_time + 0.11).contains(&time));
+                assert_eq!(count, expected_count);
+            }
+        };
+        check(burst_time(4.0), [(0.5, 1), (2.0, 10)]);
+        check(burst_time(8.0), [(0.5, 1), (4.0, 10)]);
+        // The relative burst resolves before the absolute one in a short system.
+        check(burst_time(0.6), [(0.3, 10), (0.5, 1)]);
+    }
+
+    #[test]
+    fn seeded_systems_spawn_identical_particles() {
+        let mut app = test_app();
+        let seeded = |seed| ParticleSystem {
+            spawn_rate_per_second: 50.0.into(),
+            lifetime: JitteredValue::jittered(10.0, -5.0..5.0),
+            emitter_shape: EmitterShape::circle(JitteredValue::jittered(5.0, -5.0..5.0)),
+            initial_speed: JitteredValue::jittered(10.0, -5.0..5.0),
+            texture: ParticleTexture::TextureAtlas {
+                texture: Handle::default(),
+                atlas: Handle::default(),
+                index: AtlasIndex::Random((0..64).into()),
+            },
+            seed: Some(seed),
+            ..ParticleSystem::default()
+        };
+        let first = spawn_system(&mut app, seeded(7));
+        let second = spawn_system(&mut app, seeded(7));
+        let other = spawn_system(&mut app, seeded(8));
+
+        for _ in 0..10 {
+            step(&mut app, 0.1);
+        }
+
+        let mut query = app.world_mut().query::<(
+            Entity,
+            &Particle,
+            &TextureAtlas,
+            &Velocity,
+            &DistanceTraveled,
+        )>();
+        let mut particles_of = |app: &App, system: Entity| {
+            let mut particles: Vec<_> = query
+                .iter(app.world())
+                .filter(|(_, particle, ..)| particle.parent_system == system)
+                .map(|(entity, particle, atlas, velocity, distance)| {
+                    (
+                        entity,
+                        (
+                            atlas.index,
+                            particle.max_lifetime,
+                            velocity.0,
+                            distance.from,
+                        ),
+                    )
+                })
+                .collect();
+            particles.sort_by_key(|(entity, _)| *entity);
+            particles
+                .into_iter()
+                .map(|(_, data)| data)
+                .collect::<Vec<_>>()
+        };
+        let first = particles_of(&app, first);
+        assert!(first.len() > 20);
+        assert_eq!(first, particles_of(&app, second));
+        assert_ne!(first, particles_of(&app, other));
+    }
+
+    #[test]
+    fn emit_once_fires_each_time_it_is_inserted() {
+        let mut app = test_app();
+        let system = app
+            .world_mut()
+            .spawn(ParticleSystemBundle {
+                particle_system: ParticleSystem {
+                    spawn_rate_per_second: 0.0.into(),
+                    lifetime: 100.0.into(),
+                    bursts: vec![ParticleBurst::new(0.0, 10)],
+                    ..ParticleSystem::default()
+                },
+                ..ParticleSystemBundle::default()
+            })
+            .id();
+
+        step(&mut app, 0.1);
+        assert_eq!(particle_count(&mut app), 0);
+
+        app.world_mut().entity_mut(system).insert(EmitOnce);
+        for _ in 0..3 {
+            step(&mut app, 0.1);
+            assert_eq!(particle_count(&mut app), 10);
+        }
+        assert!(!app.world().entity(system).contains::<Playing>());
+        assert!(!app.world().entity(system).contains::<EmitOnce>());
+
+        app.world_mut().entity_mut(system).insert(EmitOnce);
+        step(&mut app, 0.1);
+        step(&mut app, 0.1);
+        assert_eq!(particle_count(&mut app), 20);
+    }
+
+    #[test]
+    fn emit_once_replays_the_seeded_stream() {
+        let mut app = test_app();
+        let system = app
+            .world_mut()
+            .spawn(ParticleSystemBundle {
+                particle_system: ParticleSystem {
+                    spawn_rate_per_second: 0.0.into(),
+                    lifetime: 100.0.into(),
+                    emitter_shape: EmitterShape::circle(10.0),
+                    initial_speed: 0.0.into(),
+                    bursts: vec![ParticleBurst::new(0.0, 5)],
+                    seed: Some(3),
+                    ..ParticleSystem::default()
+                },
+                ..ParticleSystemBundle::default()
+            })
+            .id();
+        let positions = |app: &mut App| {
+            let mut query = app
+                .world_mut()
+                .query_filtered::<(Entity, &Transform), With<Particle>>();
+            let mut positions: Vec<_> = query
+                .iter(app.world())
+                .map(|(entity, transform)| (entity, transform.translation))
+                .collect();
+            positions.sort_by_key(|(entity, _)| *entity);
+            positions
+                .into_iter()
+                .map(|(_, position)| position)
+                .collect::<Vec<_>>()
+        };
+
+        app.world_mut().entity_mut(system).insert(EmitOnce);
+        step(&mut app, 0.1);
+        let first = positions(&mut app);
+        assert!(
+            app.world()
+                .get::<RunningState>(system)
+                .unwrap()
+                .seeded_draws
+                > 0
+        );
+
+        app.world_mut().entity_mut(system).insert(EmitOnce);
+        step(&mut app, 0.1);
+        let both = positions(&mut app);
+        assert_eq!(both.len(), 10);
+        assert_eq!(both[..5], first[..]);
+        assert_eq!(both[5..], first[..]);
+    }
+
+    #[cfg(feature = "custom_color")]
+    #[test]
+    fn custom_color_maps_speed_to_color() {
+        use crate::ColorFn;
+
+        let mut app = test_app();
+        let speed_color = ColorOverTime::Custom(ColorFn::new(|context| {
+            if context.speed() > 50.0 {
+                Color::srgb(1.0, 0.0, 0.0)
+            } else {
+                Color::srgb(0.0, 0.0, 1.0)
+            }
+        }));
+        let slow = spawn_system(
+            &mut app,
+            ParticleSystem {
+                initial_speed: 10.0.into(),
+                color: speed_color.clone(),
+                ..ParticleSystem::default()
+            },
+        );
+        spawn_system(
+            &mut app,
+            ParticleSystem {
+                initial_speed: 100.0.into(),
+                color: speed_color,
+                ..ParticleSystem::default()
+            },
+        );
+
+        for _ in 0..3 {
+            step(&mut app, 0.1);
+            let mut query = app.world_mut().query::<(&Particle, &Sprite)>();
+            assert!(query.iter(app.world()).count() >= 2);
+            for (particle, sprite) in query.iter(app.world()) {
+                let expected = if particle.parent_system == slow {
+                    Color::srgb(0.0, 0.0, 1.0)
+                } else {
+                    Color::srgb(1.0, 0.0, 0.0)
+                };
+                assert_eq!(sprite.color, expected);
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn populated_particle_system() -> ParticleSystem {
+        ParticleSystem {
+            max_particles: 250,
+            min_particles: 20,
+            texture: ParticleTexture::TextureAtlas {
+                texture: Handle::default(),
+                atlas: Handle::default(),
+                index: AtlasIndex::Animated(
+                    AnimatedIndex::from_fps(vec![0, 1, 2], 8.0).with_random_frame_offset(),
+                ),
+            },
+            rescale_texture: Some(Vec2::new(4.0, 8.0)),
+            spawn_rate_per_second: ValueOverTime::Sin(SinWave::default()),
+            emission_control: EmissionControl::SteadyState { target_alive: 40 },
+            emission_window: Some(0.5..1.5),
+            emitter_shape: EmitterShape::Line(
+                Line::new(12.0, JitteredValue::jittered(0.5, -0.1..0.1))
+                    .with_emit_perpendicular(false),
+            ),
+            emitter_wander: Some(Noise2D::new(1.0, 2.0, Vec2::X)),
+            emitter_spin: Some((0.0..TAU).into()),
+            spawn_grid_snap: Some(2.0),
+            interpolate_spawn_positions: true,
+            initial_speed: JitteredValue::jittered(10.0, 0.0..5.0),
+            speed_by_radius: Some(Curve::new(vec![
+                CurvePoint::new(1.0, 0.0),
+                CurvePoint::new(0.5, 1.0),
+            ])),
+            direction_spread: Some(0.25),
+            initial_velocity_override: Some(VectorOverTime::Lerp(Lerp::new(Vec3::X, Vec3::Y))),
+            initial_velocity: Some(vec![Vec3::X, Vec3::NEG_Y].into()),
+            velocity_modifiers: vec![
+                VelocityModifier::Drag(0.5.into()),
+                VelocityModifier::Noise(Noise2D::new(2.0, 3.0, Vec2::ONE)),
+                VelocityModifier::GlobalGravity(2.0),
+            ],
+            velocity_constraint: Some(VelocityConstraint {
+                lock_z: true,
+                ..VelocityConstraint::default()
+            }),
+            mass: JitteredValue::jittered(1.0, -0.5..0.5),
+            lifetime: 3.0.into(),
+            lifetime_events: vec![0.25, 0.5],
+            lifetime_by_speed: Some(Curve::new(vec![
+                CurvePoint::new(0.5, 0.0),
+                CurvePoint::new(1.0, 1.0),
+            ])),
+            initial_age: JitteredValue::jittered(0.0, 0.0..0.5),
+            color: ColorOverTime::Lerp(Lerp::new(Color::WHITE, Color::BLACK)),
+            color_layers: vec![(ColorBlendOp::Screen, Color::BLACK.into())],
+            color_driver: ColorDriver::Speed { reference: 10.0 },
+            alpha_over_time: Some(AlphaOverTime((1.0..0.0).into())),
+            flicker: Some(FlickerModifier::new(10.0, 0.25)),
+            outline: Some(ParticleOutline::default()),
+            connect_particles: Some(ConnectParticles::default()),
+            initial_scale: 2.0.into(),
+            scale: ValueOverTime::Curve(Curve::new(vec![
+                CurvePoint::new(1.0, 0.0),
+                CurvePoint::new(0.0, 1.0),
+            ])),
+            axis_scale: Vec3::new(-1.0, 2.0, 1.0),
+            initial_rotation: 1.0.into(),
+            initial_rotation_mode: InitialRotationMode::AlongTangent,
+            rotation_speed: JitteredValue::jittered(0.0, -1.0..1.0),
+            rotate_to_movement_direction: true,
+            align_to_velocity: true,
+            looping: false,
+            loop_count: LoopCount::Finite(3),
+            system_duration_seconds: 2.0,
+            max_distance: Some(100.0),
+            despawn_when_offscreen: Some(OffscreenDespawn::default()),
+            despawn_region: Some(ParticleRegion::Aabb {
+                min: Vec3::NEG_ONE,
+                max: Vec3::ONE,
+            }),
+            despawn_if_spawned_outside: true,
+            z_value_override: Some(JitteredValue::jittered(1.0, 0.0..0.1)),
+            z_offset_from_emitter: Some(-0.5),
+            max_spawns_per_frame: Some(40),
+            total_particles_to_emit: Some(500),
+            bursts: vec![
+                ParticleBurst::new(0.0, 10),
+                ParticleBurst::at_fraction(0.5, 20),
+            ],
+            repeating_burst: Some(RepeatingBurst {
+                interval: 0.1,
+                count: 2,
+                jitter: 1,
+            }),
+            deferred_bursts: true,
+            deferred_burst_timeout: 2.0,
+            space: ParticleSpace::Local,
+            seed: Some(42),
+            use_scaled_time: false,
+            max_delta_time: Some(0.1),
+            simulation_speed: 0.5,
+            simulation_speed_affects_lifetime: true,
+            despawn_on_finish: true,
+            despawn_particles_with_system: true,
+        }
+    }
+
+    #[test]
+    fn particle_system_round_trips_through_from_reflect() {
+        let particle_system = populated_particle_system();
+
+        let reflected = particle_system.clone_value();
+        let round_tripped = ParticleSystem::from_reflect(reflected.as_ref())
+            .expect("a ParticleSystem should be constructible from its reflected value");
+
+        assert_eq!(format!("{round_tripped:?}"), format!("{particle_system:?}"));
+    }
+
+    #[test]
+    fn particle_system_types_register_reflect_default() {
+        let app = test_app();
+        let registry = app.world().resource::<AppTypeRegistry>().read();
+
+        for type_id in [
+            TypeId::of::<ParticleSystem>(),
+            TypeId::of::<ParticleTexture>(),
+            TypeId::of::<AtlasIndex>(),
+            TypeId::of::<AnimatedIndex>(),
+            TypeId::of::<DistanceAnimatedIndex>(),
+            TypeId::of::<RandomValue<usize>>(),
+            TypeId::of::<RandomValue<Vec3>>(),
+            TypeId::of::<EmitterShape>(),
+            TypeId::of::<CircleSegment>(),
+            TypeId::of::<Line>(),
+            TypeId::of::<EmissionControl>(),
+            TypeId::of::<LoopCount>(),
+            TypeId::of::<JitteredValue>(),
+            TypeId::of::<InitialRotation>(),
+            TypeId::of::<InitialRotationMode>(),
+            TypeId::of::<ValueOverTime>(),
+            TypeId::of::<VectorOverTime>(),
+            TypeId::of::<ColorOverTime>(),
+            TypeId::of::<Curve<f32>>(),
+            TypeId::of::<CurvePoint<f32>>(),
+            TypeId::of::<CurvePoint<Vec3>>(),
+            TypeId::of::<CurvePoint<Color>>(),
+            TypeId::of::<VelocityModifier>(),
+            TypeId::of::<VelocityConstraint>(),
+            TypeId::of::<FlickerModifier>(),
+            TypeId::of::<ParticleOutline>(),
+            TypeId::of::<ColorBlendOp>(),
+            TypeId::of::<ColorDriver>(),
+            TypeId::of::<AlphaOverTime>(),
+            TypeId::of::<ConnectParticles>(),
+            TypeId::of::<OffscreenDespawn>(),
+            TypeId::of::<ParticleRegion>(),
+            TypeId::of::<ParticleBurst>(),
+            TypeId::of::<RepeatingBurst>(),
+            TypeId::of::<ParticleSpace>(),
+            TypeId::of::<Range<f32>>(),
+        ] {
+            let registration = registry
+                .get(type_id)
+                .unwrap_or_else(|| panic!("{type_id:?} is not registered"));
+            assert!(
+                registration.data::<ReflectDefault>().is_some(),
+                "{} does not register ReflectDefault",
+                registration.type_info().type_path()
+            );
         }
     }
 }
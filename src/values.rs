@@ -1,19 +1,27 @@
 //! Different value types and controls used in particle systems.
-use std::ops::Range;
+use std::ops::{Add, Mul, Range};
 
+use bevy_asset::Handle;
 use bevy_color::palettes::basic::FUCHSIA;
-use bevy_color::{Color, ColorRange};
+use bevy_color::{
+    Alpha, Color, ColorRange, ColorToComponents, ColorToPacked, Hsla, LinearRgba, Srgba,
+};
 use bevy_math::{vec3, Quat, Vec2, Vec3};
 use bevy_reflect::std_traits::ReflectDefault;
 use bevy_reflect::{FromReflect, Reflect};
+use bevy_render::mesh::{Mesh, PrimitiveTopology, VertexAttributeValues};
+use bevy_render::render_asset::RenderAssetUsages;
+use bevy_render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy_render::texture::Image;
 use bevy_transform::prelude::Transform;
 use rand::seq::SliceRandom;
-use rand::{prelude::ThreadRng, Rng};
+use rand::Rng;
 
-use crate::AnimatedIndex;
+use crate::{AnimatedIndex, DistanceAnimatedIndex};
 
 /// Describes an oriented segment of a circle with a given radius.
 #[derive(Debug, Clone, Reflect)]
+#[reflect(Default)]
 pub struct CircleSegment {
     /// The shape of the emitter, defined in radians.
     ///
@@ -52,7 +60,21 @@ impl From<CircleSegment> for EmitterShape {
 }
 
 /// Defines a line along which particles will be spawned.
+///
+/// The line is centered on the emitter and lies perpendicular to ``angle``: with an angle of zero it spans the
+/// Y axis and particles travel towards +X, forming a curtain. Use [`Line::with_emit_perpendicular`] to make
+/// particles travel along the line instead.
+///
+/// ## Examples
+///
+/// ```
+/// # use bevy_particle_systems::values::Line;
+/// let curtain = Line::new(100.0, 0.0);
+/// let conveyor = Line::new(100.0, 0.0).with_emit_perpendicular(false);
+/// assert!(curtain.emit_perpendicular() && !conveyor.emit_perpendicular());
+/// ```
 #[derive(Debug, Clone, Reflect)]
+#[reflect(Default)]
 pub struct Line {
     /// The lenth of the line
     pub length: f32,
@@ -61,15 +83,40 @@ pub struct Line {
     ///
     /// Zero indicates straight to the right in the +X direction. [`std::f32::consts::PI`] indicates straight left in the -X direction.
     pub angle: JitteredValue,
+
+    /// Whether particles travel perpendicular to the line, see [`Line::with_emit_perpendicular`].
+    emit_perpendicular: bool,
 }
 
-impl Default for Line {
-    fn default() -> Self {
+impl Line {
+    /// Creates a new [`Line`] with the specified length and angle in radian, emitting perpendicular to the line.
+    pub fn new(length: f32, angle: impl Into<JitteredValue>) -> Self {
         Self {
-            length: 1.0,
-            angle: 0.0.into(),
+            length,
+            angle: angle.into(),
+            emit_perpendicular: true,
         }
     }
+
+    /// Sets whether particles travel perpendicular to the line, in the direction of ``angle``.
+    ///
+    /// When `false`, particles travel along the line instead, towards ``angle`` plus a quarter turn, like a conveyor.
+    /// Defaults to `true`.
+    pub fn with_emit_perpendicular(mut self, emit_perpendicular: bool) -> Self {
+        self.emit_perpendicular = emit_perpendicular;
+        self
+    }
+
+    /// Whether particles travel perpendicular to the line, see [`Line::with_emit_perpendicular`].
+    pub fn emit_perpendicular(&self) -> bool {
+        self.emit_perpendicular
+    }
+}
+
+impl Default for Line {
+    fn default() -> Self {
+        Self::new(1.0, 0.0)
+    }
 }
 
 impl From<Line> for EmitterShape {
@@ -96,11 +143,22 @@ impl From<Line> for EmitterShape {
 /// };
 /// ```
 #[derive(Debug, Clone, Reflect)]
+#[reflect(Default)]
 pub enum EmitterShape {
     /// An oriented segment of a circle with a given radius
     CircleSegment(CircleSegment),
     /// Emit particles from a 2d line at an angle
     Line(Line),
+    /// Emit particles from random points on the surface of a mesh, moving along the surface normal.
+    ///
+    /// Points are area-weighted, so larger triangles emit more particles. The mesh must use
+    /// [`PrimitiveTopology::TriangleList`] and stay readable on the CPU (include
+    /// [`bevy_render::render_asset::RenderAssetUsages::MAIN_WORLD`]). No particles are spawned until the mesh is
+    /// loaded.
+    ///
+    /// The mesh is sampled by the particle spawner through a [`MeshSurfaceSampler`], built once per mesh and rebuilt
+    /// when the mesh is modified. [`EmitterShape::sample`] returns the emitter origin for this shape.
+    MeshSurface(Handle<Mesh>),
 }
 
 impl EmitterShape {
@@ -124,18 +182,20 @@ impl EmitterShape {
     where
         T: Into<JitteredValue>,
     {
-        Self::Line(Line {
-            length,
-            angle: angle.into(),
-        })
+        Self::Line(Line::new(length, angle))
     }
 
     /// Samples a random starting transform from the Emitter shape
     ///
+    /// Any [`Rng`] can be used, such as [`rand::rngs::ThreadRng`] or a faster non-cryptographic generator.
+    ///
     /// The returned transform describes the position and direction of movement of the newly spawned particle.
     /// (Note: The actual angle of the new particle might get overridden for a [`crate::components::ParticleSystem`] e.g if
     /// `rotate_to_movement_direction` is false.)
-    pub fn sample(&self, rng: &mut ThreadRng) -> Transform {
+    ///
+    /// [`EmitterShape::MeshSurface`] can't be sampled without its mesh asset, so this returns [`Transform::IDENTITY`],
+    /// the emitter origin, for it. Use a [`MeshSurfaceSampler`] built from the loaded mesh instead.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Transform {
         match self {
             EmitterShape::CircleSegment(CircleSegment {
                 opening_angle,
@@ -148,16 +208,152 @@ impl EmitterShape {
                 let delta = direction * radius.get_value(rng);
                 Transform::from_translation(delta).with_rotation(Quat::from_rotation_z(radian))
             }
-            EmitterShape::Line(Line { length, angle }) => {
+            EmitterShape::Line(Line {
+                length,
+                angle,
+                emit_perpendicular,
+            }) => {
                 let angle = angle.get_value(rng);
                 let distance: f32 = rng.gen_range(-0.5..0.5) * length;
 
                 let rotation = Quat::from_rotation_z(angle);
+                let direction = if *emit_perpendicular {
+                    rotation
+                } else {
+                    rotation * Quat::from_rotation_z(std::f32::consts::FRAC_PI_2)
+                };
 
                 Transform::from_translation(rotation * vec3(0.0, distance, 0.0))
-                    .with_rotation(rotation)
+                    .with_rotation(direction)
+            }
+            EmitterShape::MeshSurface(_) => Transform::IDENTITY,
+        }
+    }
+
+    /// Normalizes the distance of a sampled position from the emitter center, from `0.0` at the center to `1.0` at
+    /// the furthest possible position.
+    ///
+    /// For [`EmitterShape::CircleSegment`] this is the distance relative to the largest possible radius, and for
+    /// [`EmitterShape::Line`] the distance along the line relative to half its length, so both ends are at `1.0`.
+    /// Returns `None` for [`EmitterShape::MeshSurface`], which has no meaningful center.
+    pub fn normalized_distance(&self, position: Vec3) -> Option<f32> {
+        let max_distance = match self {
+            EmitterShape::CircleSegment(CircleSegment { radius, .. }) => {
+                radius.value + radius.jitter_range.as_ref().map_or(0.0, |range| range.end)
+            }
+            EmitterShape::Line(Line { length, .. }) => length * 0.5,
+            EmitterShape::MeshSurface(_) => return None,
+        };
+        if max_distance <= 0.0 {
+            return Some(0.0);
+        }
+        Some((position.length() / max_distance).clamp(0.0, 1.0))
+    }
+}
+
+/// Samples random points on the surface of a [`Mesh`], weighted by triangle area.
+///
+/// Used by [`EmitterShape::MeshSurface`].
+#[derive(Debug, Clone)]
+pub struct MeshSurfaceSampler {
+    triangles: Vec<[Vec3; 3]>,
+    normals: Option<Vec<[Vec3; 3]>>,
+    cumulative_areas: Vec<f32>,
+}
+
+impl MeshSurfaceSampler {
+    /// Builds a sampler from the triangles of the given mesh.
+    ///
+    /// Returns `None` if the mesh is not a [`PrimitiveTopology::TriangleList`], has no positions, or has no area.
+    pub fn new(mesh: &Mesh) -> Option<Self> {
+        if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+            return None;
+        }
+        let positions = mesh.attribute(Mesh::ATTRIBUTE_POSITION)?.as_float3()?;
+        let vertex_normals = mesh
+            .attribute(Mesh::ATTRIBUTE_NORMAL)
+            .and_then(VertexAttributeValues::as_float3);
+        let indices: Vec<usize> = match mesh.indices() {
+            Some(indices) => indices.iter().collect(),
+            None => (0..positions.len()).collect(),
+        };
+
+        let mut triangles = Vec::with_capacity(indices.len() / 3);
+        let mut normals = vertex_normals.map(|_| Vec::with_capacity(indices.len() / 3));
+        let mut cumulative_areas = Vec::with_capacity(indices.len() / 3);
+        let mut total_area = 0.0;
+        for triangle in indices.chunks_exact(3) {
+            let corners = [
+                Vec3::from(*positions.get(triangle[0])?),
+                Vec3::from(*positions.get(triangle[1])?),
+                Vec3::from(*positions.get(triangle[2])?),
+            ];
+            total_area += (corners[1] - corners[0])
+                .cross(corners[2] - corners[0])
+                .length()
+                * 0.5;
+            triangles.push(corners);
+            cumulative_areas.push(total_area);
+            if let (Some(normals), Some(vertex_normals)) = (&mut normals, vertex_normals) {
+                normals.push([
+                    Vec3::from(*vertex_normals.get(triangle[0])?),
+                    Vec3::from(*vertex_normals.get(triangle[1])?),
+                    Vec3::from(*vertex_normals.get(triangle[2])?),
+                ]);
             }
         }
+
+        if total_area <= 0.0 {
+            return None;
+        }
+
+        Some(Self {
+            triangles,
+            normals,
+            cumulative_areas,
+        })
+    }
+
+    /// Samples a random point on the mesh surface.
+    ///
+    /// The returned transform is positioned on the surface and rotated so its local X axis follows the surface
+    /// normal, matching the convention of [`EmitterShape::sample`]. Vertex normals are interpolated when the mesh
+    /// has them, otherwise the triangle's face normal is used.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Transform {
+        let total_area = self.cumulative_areas[self.cumulative_areas.len() - 1];
+        let target = rng.gen_range(0.0..total_area);
+        let index = self
+            .cumulative_areas
+            .partition_point(|area| *area <= target)
+            .min(self.triangles.len() - 1);
+        let [first, second, third] = self.triangles[index];
+
+        // Uniform barycentric coordinates, folding samples outside the triangle back inside.
+        let (mut second_weight, mut third_weight): (f32, f32) = (rng.gen(), rng.gen());
+        if second_weight + third_weight > 1.0 {
+            second_weight = 1.0 - second_weight;
+            third_weight = 1.0 - third_weight;
+        }
+        let first_weight = 1.0 - second_weight - third_weight;
+        let position = first * first_weight + second * second_weight + third * third_weight;
+
+        let normal = match &self.normals {
+            Some(normals) => {
+                let [first_normal, second_normal, third_normal] = normals[index];
+                (first_normal * first_weight
+                    + second_normal * second_weight
+                    + third_normal * third_weight)
+                    .normalize_or_zero()
+            }
+            None => (second - first).cross(third - first).normalize_or_zero(),
+        };
+
+        let rotation = if normal == Vec3::ZERO {
+            Quat::IDENTITY
+        } else {
+            Quat::from_rotation_arc(Vec3::X, normal)
+        };
+        Transform::from_translation(position).with_rotation(rotation)
     }
 }
 
@@ -167,6 +363,40 @@ impl Default for EmitterShape {
     }
 }
 
+/// How many times a looping [`crate::components::ParticleSystem`] runs through its duration, see
+/// [`crate::components::ParticleSystem::loop_count`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default)]
+pub enum LoopCount {
+    /// The system loops forever.
+    #[default]
+    Infinite,
+
+    /// The system runs through its duration this many times in total, then finishes.
+    ///
+    /// `Finite(0)` runs once, like a system that doesn't loop.
+    Finite(u32),
+}
+
+/// Defines how a [`crate::components::ParticleSystem`] decides how many particles to emit over time.
+#[derive(Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Default)]
+pub enum EmissionControl {
+    /// Emit particles at the system's ``spawn_rate_per_second``.
+    #[default]
+    Rate,
+
+    /// Emit particles at the rate needed to keep roughly ``target_alive`` particles alive at once.
+    ///
+    /// The spawn rate is derived from the average ``lifetime`` of the system, and spawning pauses while the live
+    /// count is at the target, so the population stays steady even when lifetimes have a lot of jitter.
+    /// ``spawn_rate_per_second`` is ignored, and ``target_alive`` is clamped to ``max_particles``.
+    SteadyState {
+        /// The number of live particles the system aims to maintain.
+        target_alive: usize,
+    },
+}
+
 /// A value that will be chosen from a set of possible values when read.
 ///
 /// ## Examples
@@ -199,6 +429,12 @@ pub enum RandomValue<T: Reflect + Clone + FromReflect> {
     RandomChoice(Vec<T>),
 }
 
+impl<T: Reflect + Clone + FromReflect + Default> Default for RandomValue<T> {
+    fn default() -> Self {
+        RandomValue::Constant(T::default())
+    }
+}
+
 impl<T: Reflect + Clone + FromReflect> From<T> for RandomValue<T> {
     fn from(t: T) -> Self {
         RandomValue::Constant(t)
@@ -226,7 +462,7 @@ impl<T: Reflect + Clone + FromReflect> RandomValue<T> {
     /// # Panics
     ///
     /// Will panic if there are no values to choose from
-    pub fn get_value(&self, rng: &mut ThreadRng) -> T {
+    pub fn get_value<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
         match self {
             Self::Constant(t) => t.clone(),
             Self::RandomChoice(v) => {
@@ -240,8 +476,21 @@ impl<T: Reflect + Clone + FromReflect> RandomValue<T> {
     }
 }
 
+/// An animation speed in frames per second, used to build an animated [`AtlasIndex`].
+///
+/// ## Examples
+///
+/// ```
+/// # use bevy_particle_systems::values::{AtlasIndex, Fps};
+/// // Plays frames 0 to 7 at 12 frames per second.
+/// let index: AtlasIndex = (0..8, Fps(12.0)).into();
+/// ```
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct Fps(pub f32);
+
 /// Defines an index of a texture atlas to use for a particle
 #[derive(Debug, Clone, Reflect)]
+#[reflect(Default)]
 pub enum AtlasIndex {
     /// Constant index
     Constant(usize),
@@ -249,15 +498,18 @@ pub enum AtlasIndex {
     Random(RandomValue<usize>),
     /// Animated index, to animate a sprite sheet
     Animated(AnimatedIndex),
+    /// Index animated by the distance the particle has moved, rather than time
+    DistanceAnimated(DistanceAnimatedIndex),
 }
 
 impl AtlasIndex {
     /// Returns what should be the initial value of the index, at the particle spawn
-    pub fn get_value(&self, rng: &mut ThreadRng) -> usize {
+    pub fn get_value<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
         match self {
             Self::Constant(c) => *c,
             Self::Random(r) => r.get_value(rng),
             Self::Animated(a) => a.get_at_start(),
+            Self::DistanceAnimated(d) => d.get_at_distance(0.0),
         }
     }
 }
@@ -286,6 +538,7 @@ impl From<f32> for AtlasIndex {
             indices: vec![],
             time_step: t,
             step_offset: 0,
+            random_frame_offset: false,
         })
     }
 }
@@ -296,6 +549,7 @@ impl From<(Range<usize>, f32)> for AtlasIndex {
             indices: range.collect(),
             time_step: time,
             step_offset: 0,
+            random_frame_offset: false,
         })
     }
 }
@@ -306,6 +560,7 @@ impl From<(Range<usize>, f32, usize)> for AtlasIndex {
             indices: range.collect(),
             time_step: time,
             step_offset: step,
+            random_frame_offset: false,
         })
     }
 }
@@ -316,6 +571,7 @@ impl From<(Vec<usize>, f32)> for AtlasIndex {
             indices,
             time_step: time,
             step_offset: 0,
+            random_frame_offset: false,
         })
     }
 }
@@ -326,10 +582,23 @@ impl From<(Vec<usize>, f32, usize)> for AtlasIndex {
             indices,
             time_step: time,
             step_offset: step,
+            random_frame_offset: false,
         })
     }
 }
 
+impl From<(Range<usize>, Fps)> for AtlasIndex {
+    fn from((range, fps): (Range<usize>, Fps)) -> Self {
+        AtlasIndex::Animated(AnimatedIndex::from_fps(range.collect(), fps.0))
+    }
+}
+
+impl From<(Vec<usize>, Fps)> for AtlasIndex {
+    fn from((indices, fps): (Vec<usize>, Fps)) -> Self {
+        AtlasIndex::Animated(AnimatedIndex::from_fps(indices, fps.0))
+    }
+}
+
 impl Default for AtlasIndex {
     fn default() -> Self {
         AtlasIndex::Constant(0)
@@ -368,7 +637,8 @@ impl Default for AtlasIndex {
 ///     assert!(value >= 5.0);
 /// }
 /// ```
-#[derive(Debug, Clone, Reflect)]
+#[derive(Debug, Clone, Default, Reflect)]
+#[reflect(Default)]
 pub struct JitteredValue {
     /// The base value that specified jitter will be added to.
     pub value: f32,
@@ -414,8 +684,24 @@ impl JitteredValue {
         }
     }
 
+    /// Get the average value that [`JitteredValue::get_value`] will return over time.
+    pub fn average(&self) -> f32 {
+        match &self.jitter_range {
+            Some(r) => self.value + (r.start + r.end) / 2.0,
+            None => self.value,
+        }
+    }
+
+    /// Get the largest value that [`JitteredValue::get_value`] can return.
+    pub fn max(&self) -> f32 {
+        match &self.jitter_range {
+            Some(r) => self.value + r.end,
+            None => self.value,
+        }
+    }
+
     /// Get a value with random jitter within ``jitter_range`` added to it.
-    pub fn get_value(&self, rng: &mut ThreadRng) -> f32 {
+    pub fn get_value<R: Rng + ?Sized>(&self, rng: &mut R) -> f32 {
         match &self.jitter_range {
             Some(r) => self.value + rng.gen_range(r.clone()),
             None => self.value,
@@ -435,6 +721,203 @@ impl From<Range<f32>> for JitteredValue {
     }
 }
 
+/// The rotation of a particle around the `z` axis when it spawns, in radians.
+///
+/// ## Examples
+///
+/// ```
+/// # use std::f32::consts::{FRAC_PI_2, PI};
+/// # use bevy_particle_systems::values::{InitialRotation, JitteredValue};
+/// // Every particle spawns upright.
+/// let upright: InitialRotation = 0.0.into();
+///
+/// // Particles spawn turned up to a quarter turn either way.
+/// let tilted = InitialRotation::symmetric(FRAC_PI_2);
+///
+/// // Particles land on cardinal angles, like tiles.
+/// let cardinal: InitialRotation = vec![0.0, FRAC_PI_2, PI, 3.0 * FRAC_PI_2].into();
+/// ```
+#[derive(Debug, Clone, Reflect)]
+#[reflect(Default)]
+pub enum InitialRotation {
+    /// A rotation with optional random jitter.
+    Jittered(JitteredValue),
+
+    /// A rotation picked at random from a set of angles.
+    ///
+    /// An empty set spawns particles unrotated.
+    Discrete(Vec<f32>),
+}
+
+impl Default for InitialRotation {
+    fn default() -> Self {
+        Self::Jittered(JitteredValue::default())
+    }
+}
+
+impl InitialRotation {
+    /// A rotation picked uniformly between ``-max_angle`` and ``max_angle``.
+    pub fn symmetric(max_angle: f32) -> Self {
+        Self::Jittered(JitteredValue::jittered(0.0, -max_angle..max_angle))
+    }
+
+    /// Get a rotation, in radians.
+    pub fn get_value<R: Rng + ?Sized>(&self, rng: &mut R) -> f32 {
+        match self {
+            Self::Jittered(jittered) => jittered.get_value(rng),
+            Self::Discrete(angles) => angles.choose(rng).copied().unwrap_or_default(),
+        }
+    }
+}
+
+impl From<f32> for InitialRotation {
+    fn from(angle: f32) -> Self {
+        Self::Jittered(angle.into())
+    }
+}
+
+impl From<JitteredValue> for InitialRotation {
+    fn from(jittered: JitteredValue) -> Self {
+        Self::Jittered(jittered)
+    }
+}
+
+impl From<Vec<f32>> for InitialRotation {
+    fn from(angles: Vec<f32>) -> Self {
+        Self::Discrete(angles)
+    }
+}
+
+impl From<RandomValue<f32>> for InitialRotation {
+    fn from(value: RandomValue<f32>) -> Self {
+        match value {
+            RandomValue::Constant(angle) => angle.into(),
+            RandomValue::RandomChoice(angles) => Self::Discrete(angles),
+        }
+    }
+}
+
+/// Sets the base rotation of a particle at spawn from where it spawns around the emitter.
+///
+/// The particle's ``initial_rotation`` is added on top of the base rotation. This is independent of the particle's
+/// velocity, so particles can e.g. face outward while moving in another direction.
+///
+/// ## Examples
+///
+/// ```
+/// # use bevy_particle_systems::{EmitterShape, InitialRotationMode, ParticleSystem};
+/// // Petals arranged around a flower, each pointing away from its center.
+/// let petals = ParticleSystem {
+///     emitter_shape: EmitterShape::circle(20.0),
+///     initial_rotation_mode: InitialRotationMode::FaceOutward,
+///     initial_speed: 0.0.into(),
+///     ..ParticleSystem::default()
+/// };
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Default)]
+pub enum InitialRotationMode {
+    /// Particles only use their ``initial_rotation``, or face their movement direction when
+    /// ``rotate_to_movement_direction`` is set.
+    #[default]
+    Fixed,
+
+    /// The particle's `x` axis points away from the emitter's center.
+    FaceOutward,
+
+    /// The particle's `x` axis points towards the emitter's center.
+    FaceInward,
+
+    /// The particle's `x` axis points along the circle around the emitter's center, counter-clockwise.
+    AlongTangent,
+}
+
+impl InitialRotationMode {
+    /// The base rotation around the `z` axis, in radians, for a particle spawning at ``offset`` from the emitter's
+    /// center.
+    ///
+    /// Returns `None` for [`InitialRotationMode::Fixed`]. Particles spawning exactly at the center use the
+    /// emitter's `x` axis as their outward direction.
+    pub fn angle(self, offset: Vec2) -> Option<f32> {
+        let outward = offset.y.atan2(offset.x);
+        match self {
+            Self::Fixed => None,
+            Self::FaceOutward => Some(outward),
+            Self::FaceInward => Some(outward + std::f32::consts::PI),
+            Self::AlongTangent => Some(outward + std::f32::consts::FRAC_PI_2),
+        }
+    }
+}
+
+/// An angle, to write the radian based angle fields in degrees without converting them by hand.
+///
+/// Angles are stored in radians, and convert into the `f32`, [`JitteredValue`] and [`InitialRotation`] fields
+/// expecting radians. A range of angles converts into a [`JitteredValue`] centered within it.
+///
+/// ## Examples
+///
+/// ```
+/// # use std::f32::consts::FRAC_PI_2;
+/// # use bevy_particle_systems::values::{Angle, CircleSegment, JitteredValue};
+/// let upwards = CircleSegment {
+///     direction_angle: Angle::degrees(90.0).into(),
+///     opening_angle: Angle::degrees(30.0).into(),
+///     ..CircleSegment::default()
+/// };
+/// assert_eq!(upwards.direction_angle, FRAC_PI_2);
+///
+/// let spin: JitteredValue = (Angle::degrees(-180.0)..Angle::degrees(180.0)).into();
+/// assert_eq!(spin.max(), std::f32::consts::PI);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Angle(f32);
+
+impl Angle {
+    /// An angle of ``degrees`` degrees.
+    pub fn degrees(degrees: f32) -> Self {
+        Self(degrees.to_radians())
+    }
+
+    /// An angle of ``radians`` radians.
+    pub const fn radians(radians: f32) -> Self {
+        Self(radians)
+    }
+
+    /// The angle in degrees.
+    pub fn to_degrees(self) -> f32 {
+        self.0.to_degrees()
+    }
+
+    /// The angle in radians.
+    pub const fn to_radians(self) -> f32 {
+        self.0
+    }
+}
+
+impl From<Angle> for f32 {
+    fn from(angle: Angle) -> Self {
+        angle.to_radians()
+    }
+}
+
+impl From<Angle> for JitteredValue {
+    fn from(angle: Angle) -> Self {
+        JitteredValue::new(angle.to_radians())
+    }
+}
+
+impl From<Range<Angle>> for JitteredValue {
+    fn from(range: Range<Angle>) -> Self {
+        JitteredValue::centered_range(range.start.to_radians()..range.end.to_radians())
+    }
+}
+
+impl From<Angle> for InitialRotation {
+    fn from(angle: Angle) -> Self {
+        angle.to_radians().into()
+    }
+}
+
 /// Linearly interpolates between two values by a given percentage.
 ///
 /// ``pct`` should be between `0.0` and `1.0`, but it is up to the trait implementor to ensure
@@ -554,6 +1037,21 @@ where
     pub point: f32,
 }
 
+impl<T> Default for CurvePoint<T>
+where
+    T: Lerpable<T> + ErrorDefault<T> + Copy + Reflect + FromReflect + Default,
+{
+    fn default() -> Self {
+        Self::new(T::default(), 0.0)
+    }
+}
+
+impl<C: Into<Color>> From<(f32, C)> for CurvePoint<Color> {
+    fn from((point, color): (f32, C)) -> Self {
+        Self::new(color.into(), point)
+    }
+}
+
 impl<T> CurvePoint<T>
 where
     T: Lerpable<T> + ErrorDefault<T> + Copy + Reflect + FromReflect,
@@ -699,7 +1197,6 @@ where
                     (clamped_pct - current_point) / (next_point - current_point).abs(),
                 );
             }
-            continue;
         }
 
         T::get_error_default()
@@ -750,18 +1247,148 @@ where
                         / (self.points[i + 1].point - self.points[i].point).abs(),
                 );
             }
-            continue;
         }
 
         T::get_error_default()
     }
+
+    /// Samples ``count`` evenly spaced points from 0.0 to 1.0 inclusive, e.g. for previews or lookup tables.
+    ///
+    /// Uses [`Curve::sample_mut`], so the `index_hint` walks forward through the curve once. A ``count`` of 1 samples
+    /// only the start of the curve, and a ``count`` of 0 returns no values.
+    ///
+    /// ## Examples
+    /// ```
+    /// # use bevy_particle_systems::values::{CurvePoint, Curve};
+    /// let mut curve = Curve::new(vec![CurvePoint::new(0.0, 0.0), CurvePoint::new(4.0, 1.0)]);
+    /// assert_eq!(curve.sample_range(5), vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+    /// ```
+    #[allow(clippy::cast_precision_loss)]
+    pub fn sample_range(&mut self, count: usize) -> Vec<T> {
+        if count < 2 {
+            return (0..count).map(|_| self.sample_mut(0.0)).collect();
+        }
+
+        let last = (count - 1) as f32;
+        (0..count)
+            .map(|i| self.sample_mut(i as f32 / last))
+            .collect()
+    }
+
+    /// Samples the curve at every one of ``pcts``.
+    ///
+    /// Uses [`Curve::sample_mut`], so ``pcts`` sorted in ascending order are sampled fastest. Unsorted values still
+    /// give correct results.
+    pub fn sample_many(&mut self, pcts: &[f32]) -> Vec<T> {
+        pcts.iter().map(|&pct| self.sample_mut(pct)).collect()
+    }
+
+    /// Returns a new curve with ``f`` applied to the value of every point.
+    ///
+    /// ## Examples
+    /// ```
+    /// # use bevy_particle_systems::values::{CurvePoint, Curve};
+    /// let curve = Curve::new(vec![CurvePoint::new(1.0, 0.0), CurvePoint::new(2.0, 1.0)]);
+    /// assert_eq!(curve.mapped(|v| v * v).sample(1.0), 4.0);
+    /// ```
+    pub fn mapped(&self, f: impl Fn(T) -> T) -> Self {
+        Self::new(
+            self.points
+                .iter()
+                .map(|p| CurvePoint::new(f(p.value), p.point))
+                .collect(),
+        )
+    }
+
+    /// Returns a new curve mirrored across the middle of its range, so it plays backwards.
+    ///
+    /// Every point is moved to `1.0 - point`, keeping the points sorted.
+    pub fn reversed(&self) -> Self {
+        Self::new(
+            self.points
+                .iter()
+                .rev()
+                .map(|p| CurvePoint::new(p.value, 1.0 - p.point))
+                .collect(),
+        )
+    }
+}
+
+impl<T> Curve<T>
+where
+    T: Lerpable<T>
+        + ErrorDefault<T>
+        + Copy
+        + Reflect
+        + FromReflect
+        + Default
+        + Mul<f32, Output = T>
+        + Add<Output = T>,
+{
+    /// Returns a new curve with the value of every point multiplied by ``factor``.
+    pub fn scaled(&self, factor: f32) -> Self {
+        self.mapped(|v| v * factor)
+    }
+
+    /// Returns a new curve with ``delta`` added to the value of every point.
+    pub fn offset(&self, delta: T) -> Self {
+        self.mapped(|v| v + delta)
+    }
+}
+
+impl Curve<Color> {
+    /// Bakes the curve into a gradient texture, ``width`` pixels wide and one pixel high, for sampling by lifetime
+    /// percentage in shaders.
+    ///
+    /// Each pixel holds the curve sampled at its position, from 0.0 at the first pixel to 1.0 at the last. The image
+    /// uses [`TextureFormat::Rgba8UnormSrgb`] with straight, not premultiplied, alpha, so transparent points keep their
+    /// color and sampling it in a shader returns linear colors. Shaders blending with premultiplied alpha should
+    /// multiply the sampled color by its alpha themselves. A ``width`` of 0 is treated as 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if ``width`` does not fit in a `u32`.
+    ///
+    /// ## Examples
+    /// ```
+    /// # use bevy::prelude::Color;
+    /// # use bevy_particle_systems::values::{CurvePoint, Curve};
+    /// let mut curve = Curve::new(vec![CurvePoint::new(Color::BLACK, 0.0), CurvePoint::new(Color::WHITE, 1.0)]);
+    /// let image = curve.bake_to_image(64);
+    /// assert_eq!(image.width(), 64);
+    /// assert_eq!(image.height(), 1);
+    /// ```
+    pub fn bake_to_image(&mut self, width: usize) -> Image {
+        let width = width.max(1);
+        let data = self
+            .sample_range(width)
+            .into_iter()
+            .flat_map(|color| Srgba::from(color).to_u8_array())
+            .collect();
+
+        Image::new(
+            Extent3d {
+                width: u32::try_from(width).expect("gradient width should fit in a u32"),
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        )
+    }
 }
 
 /// Defines how a color changes over time
 ///
 /// Colors can either be constant, linearly interpolated, or follow a [`crate::values::Curve`].
+///
+/// This enum is non-exhaustive, as the `custom_color` feature adds a variant to it. Matches outside of this crate
+/// need a wildcard arm, so they keep compiling whether or not another crate in the tree enables the feature.
 #[derive(Debug, Clone, Reflect)]
 #[reflect(Default)]
+#[non_exhaustive]
 pub enum ColorOverTime {
     /// Specifies that a color should remain a constant color over time.
     Constant(Color),
@@ -771,6 +1398,218 @@ pub enum ColorOverTime {
 
     /// Specifies that a color will follow a curve of two or more colors over time.
     Gradient(Curve<Color>),
+
+    /// Specifies that a color should be linearly interpolated between two colors over time, where both colors
+    /// follow a curve over the duration of the system.
+    ///
+    /// The particle's lifetime drives the interpolation from ``a`` to ``b``, while the progress of the system when
+    /// the particle spawned picks ``a`` and ``b`` from their curves. This lets the palette of an effect evolve, such
+    /// as a fire turning from yellow to red as it burns out, while every particle still fades over its own life.
+    AnimatedLerp {
+        /// The color at the start of a particle's lifetime, over the duration of the system.
+        a: Curve<Color>,
+        /// The color at the end of a particle's lifetime, over the duration of the system.
+        b: Curve<Color>,
+    },
+
+    /// Specifies that a color is computed by a function of the particle's state every frame.
+    ///
+    /// This requires the `custom_color` feature. The function is called from several threads at once and is
+    /// skipped by reflection.
+    #[cfg(feature = "custom_color")]
+    Custom(#[reflect(ignore)] ColorFn),
+}
+
+/// Drives the alpha of a particle over its lifetime, leaving its red, green and blue channels untouched.
+///
+/// This makes fading a constant color simple, without repeating the color at every point of a gradient.
+/// Values are clamped to `[0.0, 1.0]`.
+///
+/// ## Examples
+///
+/// ```
+/// # use bevy::color::palettes::basic::AQUA;
+/// # use bevy_particle_systems::{AlphaOverTime, ColorOverTime, ParticleSystem};
+/// // Aqua particles fading out over their lifetime.
+/// let fading = ParticleSystem {
+///     color: ColorOverTime::Constant(AQUA.into()),
+///     alpha_over_time: Some(AlphaOverTime((1.0..0.0).into())),
+///     ..ParticleSystem::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Reflect)]
+#[reflect(Default)]
+pub struct AlphaOverTime(pub ValueOverTime);
+
+impl Default for AlphaOverTime {
+    fn default() -> Self {
+        Self(1.0.into())
+    }
+}
+
+impl AlphaOverTime {
+    /// Replaces the alpha of ``color`` with the alpha at the specified lifetime percentage.
+    pub fn apply(&self, color: Color, pct: f32) -> Color {
+        color.with_alpha(self.0.at_lifetime_pct(pct).clamp(0.0, 1.0))
+    }
+}
+
+/// How a color layer is composited onto the colors below it, see [`crate::ParticleSystem::color_layers`].
+///
+/// Colors are blended per channel in linear space, alpha included, and each channel is clamped to `[0.0, 1.0]`
+/// after every layer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default)]
+pub enum ColorBlendOp {
+    /// Adds the layer to the color below, brightening it.
+    ///
+    /// The alpha of the layer is added as well, so use a transparent layer to leave the opacity unchanged.
+    #[default]
+    Add,
+
+    /// Multiplies the color below by the layer, darkening it.
+    Multiply,
+
+    /// Inverts both colors, multiplies them and inverts the result, brightening the color below without
+    /// overshooting white.
+    Screen,
+}
+
+impl ColorBlendOp {
+    /// Composites ``layer`` onto ``base``.
+    pub fn blend(self, base: Color, layer: Color) -> Color {
+        let base = base.to_linear().to_f32_array();
+        let layer = layer.to_linear().to_f32_array();
+        let blend = |a: f32, b: f32| {
+            match self {
+                Self::Add => a + b,
+                Self::Multiply => a * b,
+                Self::Screen => 1.0 - (1.0 - a) * (1.0 - b),
+            }
+            .clamp(0.0, 1.0)
+        };
+        LinearRgba::from_f32_array(std::array::from_fn(|i| blend(base[i], layer[i]))).into()
+    }
+}
+
+/// Selects what drives a particle's [`ColorOverTime`] along its `0.0` to `1.0` range, see
+/// [`crate::ParticleSystem::color_driver`].
+///
+/// Speed and distance are divided by their ``reference`` and clamped to `[0.0, 1.0]`, so a particle at or beyond the
+/// reference gets the color at the end of the gradient.
+///
+/// ## Examples
+///
+/// ```
+/// # use bevy_particle_systems::ColorDriver;
+/// let hot_when_fast = ColorDriver::Speed { reference: 200.0 };
+/// assert_eq!(hot_when_fast.value(0.1, 100.0, 0.0), 0.5);
+/// assert_eq!(hot_when_fast.value(0.1, 500.0, 0.0), 1.0);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Reflect)]
+#[reflect(Default)]
+pub enum ColorDriver {
+    /// The percentage of its lifetime the particle has lived.
+    #[default]
+    Lifetime,
+
+    /// The current speed of the particle, relative to ``reference``.
+    Speed {
+        /// The speed, in world units per second, mapped to `1.0`.
+        reference: f32,
+    },
+
+    /// The distance the particle traveled since it spawned, relative to ``reference``.
+    Distance {
+        /// The distance, in world units, mapped to `1.0`.
+        reference: f32,
+    },
+}
+
+impl ColorDriver {
+    /// The value driving the color of a particle in the given state, from `0.0` to `1.0`.
+    ///
+    /// A ``reference`` that isn't positive maps any movement to `1.0`.
+    pub fn value(self, lifetime_pct: f32, speed: f32, distance: f32) -> f32 {
+        let relative = |value: f32, reference: f32| {
+            if reference > 0.0 {
+                (value / reference).clamp(0.0, 1.0)
+            } else if value > 0.0 {
+                1.0
+            } else {
+                0.0
+            }
+        };
+        match self {
+            Self::Lifetime => lifetime_pct,
+            Self::Speed { reference } => relative(speed, reference),
+            Self::Distance { reference } => relative(distance, reference),
+        }
+    }
+}
+
+/// The state of a particle used to evaluate its color.
+///
+/// This is passed to [`ColorOverTime::Custom`] functions.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ColorContext {
+    /// The percentage of its lifetime the particle has lived, from `0.0` to `1.0`.
+    pub lifetime_pct: f32,
+    /// The current velocity of the particle.
+    pub velocity: Vec3,
+    /// The position of the particle, in world space or relative to the emitter depending on its
+    /// [`crate::components::ParticleSpace`].
+    pub position: Vec3,
+    /// The random value chosen for the particle on spawn, see [`crate::components::Particle::seed`].
+    pub seed: u32,
+    /// How far through its duration the system was when the particle spawned, see
+    /// [`crate::components::Particle::system_pct`].
+    pub system_pct: f32,
+}
+
+impl ColorContext {
+    /// The current speed of the particle.
+    pub fn speed(&self) -> f32 {
+        self.velocity.length()
+    }
+}
+
+/// A thread-safe function computing a particle's color, used by [`ColorOverTime::Custom`].
+///
+/// ## Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_particle_systems::{ColorFn, ColorOverTime};
+/// // Fast particles are red, slow ones are blue.
+/// let color = ColorOverTime::Custom(ColorFn::new(|context| {
+///     Color::hsl(240.0 * (1.0 - (context.speed() / 100.0).min(1.0)), 1.0, 0.5)
+/// }));
+/// ```
+#[cfg(feature = "custom_color")]
+#[derive(Clone)]
+pub struct ColorFn(pub std::sync::Arc<dyn Fn(ColorContext) -> Color + Send + Sync>);
+
+#[cfg(feature = "custom_color")]
+impl ColorFn {
+    /// Wraps a function computing a particle's color.
+    pub fn new(function: impl Fn(ColorContext) -> Color + Send + Sync + 'static) -> Self {
+        Self(std::sync::Arc::new(function))
+    }
+}
+
+#[cfg(feature = "custom_color")]
+impl std::fmt::Debug for ColorFn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ColorFn")
+    }
+}
+
+#[cfg(feature = "custom_color")]
+impl Default for ColorFn {
+    fn default() -> Self {
+        Self::new(|_| Color::WHITE)
+    }
 }
 
 impl Default for ColorOverTime {
@@ -785,6 +1624,24 @@ impl From<Color> for ColorOverTime {
     }
 }
 
+impl From<Srgba> for ColorOverTime {
+    fn from(color: Srgba) -> Self {
+        ColorOverTime::Constant(color.into())
+    }
+}
+
+impl From<LinearRgba> for ColorOverTime {
+    fn from(color: LinearRgba) -> Self {
+        ColorOverTime::Constant(color.into())
+    }
+}
+
+impl From<Hsla> for ColorOverTime {
+    fn from(color: Hsla) -> Self {
+        ColorOverTime::Constant(color.into())
+    }
+}
+
 impl From<Range<Color>> for ColorOverTime {
     fn from(r: Range<Color>) -> Self {
         ColorOverTime::Lerp(Lerp::new(r.start, r.end))
@@ -802,14 +1659,66 @@ impl From<Vec<CurvePoint<Color>>> for ColorOverTime {
 }
 
 impl ColorOverTime {
+    /// Creates a gradient from ``(point, color)`` pairs, where ``point`` is between `0.0` and `1.0` inclusive.
+    ///
+    /// Colors can be any type convertible to [`Color`], such as [`Srgba`], [`LinearRgba`], [`Hsla`] or the
+    /// [`bevy_color::palettes`] constants. The points are sorted, so they can be given in any order. Like converting
+    /// from a `Vec<CurvePoint<Color>>`, two points spanning the whole lifetime result in a [`ColorOverTime::Lerp`].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use bevy::color::{palettes::basic::{RED, YELLOW}, Srgba};
+    /// # use bevy_particle_systems::values::ColorOverTime;
+    /// let fire = ColorOverTime::gradient([
+    ///     (0.0, YELLOW),
+    ///     (0.5, RED),
+    ///     (1.0, Srgba::new(0.2, 0.2, 0.2, 0.0)),
+    /// ]);
+    /// assert_eq!(fire.at_lifetime_pct(0.5), RED.into());
+    ///
+    /// // Constant colors and curve points convert from Bevy's color types as well.
+    /// # use bevy::color::{Color, Hsla};
+    /// # use bevy_particle_systems::values::CurvePoint;
+    /// let glow: ColorOverTime = Hsla::hsl(200.0, 1.0, 0.5).into();
+    /// let point: CurvePoint<Color> = (0.25, YELLOW).into();
+    /// ```
+    pub fn gradient<C: Into<Color>>(points: impl IntoIterator<Item = (f32, C)>) -> Self {
+        let mut points: Vec<CurvePoint<Color>> = points.into_iter().map(CurvePoint::from).collect();
+        points.sort_by(|a, b| a.point.total_cmp(&b.point));
+        points.into()
+    }
+
     /// Evaluate a color at the specified lifetime percentage.
     ///
-    /// ``pct`` should be between `0.0` and `1.0` inclusive.
+    /// ``pct`` should be between `0.0` and `1.0` inclusive. Custom colors are evaluated for a particle at rest at
+    /// the origin, see [`ColorOverTime::evaluate`] to provide the full particle state.
     pub fn at_lifetime_pct(&self, pct: f32) -> Color {
+        self.evaluate(&ColorContext {
+            lifetime_pct: pct,
+            ..ColorContext::default()
+        })
+    }
+
+    /// Evaluate a color for a particle in the given state.
+    pub fn evaluate(&self, context: &ColorContext) -> Color {
+        self.evaluate_at(context.lifetime_pct, context)
+    }
+
+    /// Evaluate a color at ``pct``, which may be driven by something other than the lifetime, see [`ColorDriver`].
+    ///
+    /// Custom colors are evaluated with the full ``context`` instead.
+    #[cfg_attr(not(feature = "custom_color"), allow(unused_variables))]
+    pub(crate) fn evaluate_at(&self, pct: f32, context: &ColorContext) -> Color {
         match self {
             Self::Constant(c) => *c,
             Self::Lerp(l) => l.a.lerp(l.b, pct),
             Self::Gradient(g) => g.sample(pct),
+            Self::AnimatedLerp { a, b } => a
+                .sample(context.system_pct)
+                .lerp(b.sample(context.system_pct), pct),
+            #[cfg(feature = "custom_color")]
+            Self::Custom(function) => (function.0)(*context),
         }
     }
 }
@@ -948,6 +1857,17 @@ impl ValueOverTime {
             Self::Constant(c) => *c,
         }
     }
+
+    /// Whether the value is above `0.0` anywhere along its lifetime.
+    pub(crate) fn can_be_positive(&self) -> bool {
+        match self {
+            Self::Lerp(l) => l.a > 0.0 || l.b > 0.0,
+            // Curves are linear between their points, so they peak at one of them.
+            Self::Curve(c) => c.points.iter().any(|point| point.value > 0.0),
+            Self::Sin(s) => s.vertical_shift + s.amplitude.abs() > 0.0,
+            Self::Constant(c) => *c > 0.0,
+        }
+    }
 }
 
 /// Defines a value that will linearly move between ``a`` and ``b`` over its configured lifetime.
@@ -1076,18 +1996,88 @@ impl Noise2D {
     }
 }
 
+/// Randomly flickers the alpha of particles, useful for fire and electricity.
+///
+/// Each particle flickers independently, based on its [`crate::components::Particle::seed`].
+#[derive(Debug, Clone, Copy, Reflect)]
+#[reflect(Default)]
+pub struct FlickerModifier {
+    /// How many times per second the flicker picks a new value.
+    pub frequency: f32,
+    /// How much the alpha can be reduced, from `0.0` (no flicker) to `1.0` (fully transparent at times).
+    pub intensity: f32,
+}
+
+impl Default for FlickerModifier {
+    fn default() -> Self {
+        Self {
+            frequency: 20.0,
+            intensity: 0.5,
+        }
+    }
+}
+
+impl FlickerModifier {
+    /// Creates a new `FlickerModifier`
+    pub fn new(frequency: f32, intensity: f32) -> Self {
+        Self {
+            frequency,
+            intensity,
+        }
+    }
+
+    /// Returns the multiplier to apply to a particle's alpha at the given lifetime, in `1.0 - intensity..=1.0`.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    pub fn sample(&self, seed: u32, time: f32) -> f32 {
+        let intensity = self.intensity.clamp(0.0, 1.0);
+        if intensity <= 0.0 {
+            return 1.0;
+        }
+        let step = (time * self.frequency.max(0.0)) as u32;
+        // A cheap integer hash, so each particle and step gets an unrelated value.
+        let mut hash = seed ^ step.wrapping_mul(0x9E37_79B9);
+        hash ^= hash >> 16;
+        hash = hash.wrapping_mul(0x7FEB_352D);
+        hash ^= hash >> 15;
+        hash = hash.wrapping_mul(0x846C_A68B);
+        hash ^= hash >> 16;
+        let noise = (hash >> 8) as f32 / 16_777_216.0;
+        1.0 - intensity * noise
+    }
+}
+
 /// Defines an acceleration modifier that will affect particles velocity.
 #[derive(Debug, Clone, Reflect)]
 #[reflect(Default)]
 pub enum VelocityModifier {
     /// f32 value that will use the direction of the current velocity.
     Scalar(ValueOverTime),
-    /// Constant vector acceleration, such as gravity.
+    /// Constant vector force, such as wind, divided by the particle mass.
+    ///
+    /// Use ``GlobalGravity`` for gravity, which accelerates every mass alike.
     Vector(VectorOverTime),
-    /// Force that will slow down the particles like air resistance.
+    /// Force that will slow down the particles like air resistance, divided by the particle mass.
     Drag(ValueOverTime),
     /// Sinusoidal 2D Noise
     Noise(Noise2D),
+    /// Acceleration from the [`crate::resources::GlobalParticleGravity`] resource, multiplied by this value.
+    GlobalGravity(f32),
+    /// Tangential force making particles orbit around a point, such as a vortex.
+    ///
+    /// The force is perpendicular to both the direction towards ``center`` and ``axis``. Combine it with
+    /// an attracting force to make particles spiral inwards.
+    Orbit {
+        /// The point to orbit around, in the same space as the particles.
+        center: Vec3,
+        /// The acceleration applied along the tangent.
+        strength: ValueOverTime,
+        /// The axis to orbit around, such as [`Vec3::Z`] for 2D.
+        axis: Vec3,
+    },
 }
 
 impl Default for VelocityModifier {
@@ -1096,6 +2086,32 @@ impl Default for VelocityModifier {
     }
 }
 
+/// Locks axes of a particle's velocity, keeping it from moving along them.
+///
+/// This generalizes the z lock used for 2D, for example to keep 3D particles in a horizontal plane by locking ``y``.
+/// Locking every axis freezes particles in place.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default)]
+pub struct VelocityConstraint {
+    /// Whether the velocity along the X axis is zeroed.
+    pub lock_x: bool,
+    /// Whether the velocity along the Y axis is zeroed.
+    pub lock_y: bool,
+    /// Whether the velocity along the Z axis is zeroed.
+    pub lock_z: bool,
+}
+
+impl VelocityConstraint {
+    /// Returns ``velocity`` with the locked axes zeroed.
+    pub fn apply(&self, velocity: Vec3) -> Vec3 {
+        Vec3::new(
+            if self.lock_x { 0.0 } else { velocity.x },
+            if self.lock_y { 0.0 } else { velocity.y },
+            if self.lock_z { 0.0 } else { velocity.z },
+        )
+    }
+}
+
 /// Setup optional values used so that every calculated values are not re-calculated for every modifiers that uses it
 pub struct PrecalculatedParticleVariables {
     /// velocity squared length
@@ -1155,8 +2171,140 @@ impl Default for PrecalculatedParticleVariables {
 
 #[cfg(test)]
 mod tests {
-    use super::{Curve, CurvePoint, JitteredValue};
+    use super::{
+        AtlasIndex, ColorBlendOp, ColorOverTime, Curve, CurvePoint, EmitterShape, FlickerModifier,
+        Fps, JitteredValue, Line, MeshSurfaceSampler, RandomValue, VelocityConstraint,
+    };
     use approx::assert_relative_eq;
+    use bevy_color::{Alpha, Color, LinearRgba, Srgba};
+    use bevy_math::{Quat, Vec3};
+    use bevy_render::{
+        mesh::{Indices, Mesh, PrimitiveTopology},
+        render_asset::RenderAssetUsages,
+    };
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    #[test]
+    fn sampling_accepts_any_rng() {
+        let mut rng = SmallRng::seed_from_u64(42);
+
+        let jittered = JitteredValue::jittered(10.0, -5.0..5.0);
+        let value = jittered.get_value(&mut rng);
+        assert!((5.0..15.0).contains(&value));
+
+        let random: RandomValue<usize> = vec![1, 2, 3].into();
+        assert!([1, 2, 3].contains(&random.get_value(&mut rng)));
+
+        let index: AtlasIndex = (4..8).into();
+        assert!((4..8).contains(&index.get_value(&mut rng)));
+
+        let transform = EmitterShape::circle(2.0).sample(&mut rng);
+        assert_relative_eq!(transform.translation.length(), 2.0, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn atlas_index_from_fps() {
+        let AtlasIndex::Animated(animated) = AtlasIndex::from((0..4, Fps(10.0))) else {
+            panic!("expected an animated index");
+        };
+        assert_relative_eq!(animated.time_step, 0.1);
+        assert_eq!(animated.get_at_time(0.25), 2);
+        assert_eq!(animated.get_at_time(0.45), 0);
+    }
+
+    #[test]
+    fn atlas_index_from_zero_fps_is_static() {
+        let AtlasIndex::Animated(animated) = AtlasIndex::from((vec![3, 5], Fps(0.0))) else {
+            panic!("expected an animated index");
+        };
+        assert_eq!(animated.get_at_time(0.0), 3);
+        assert_eq!(animated.get_at_time(100.0), 3);
+    }
+
+    fn pop_curve() -> Curve<f32> {
+        Curve::new(vec![
+            CurvePoint::new(0.0, 0.0),
+            CurvePoint::new(1.0, 0.25),
+            CurvePoint::new(0.5, 1.0),
+        ])
+    }
+
+    #[test]
+    fn curve_scaled() {
+        let curve = pop_curve().scaled(2.0);
+        assert_relative_eq!(curve.sample(0.25), 2.0);
+        assert_relative_eq!(curve.sample(1.0), 1.0);
+    }
+
+    #[test]
+    fn curve_offset() {
+        let curve = pop_curve().offset(0.5);
+        assert_relative_eq!(curve.sample(0.0), 0.5);
+        assert_relative_eq!(curve.sample(0.25), 1.5);
+    }
+
+    #[test]
+    fn curve_reversed() {
+        let original = pop_curve();
+        let curve = original.reversed();
+        for pct in [0.0, 0.1, 0.5, 0.75, 0.9, 1.0] {
+            assert_relative_eq!(
+                curve.sample(pct),
+                original.sample(1.0 - pct),
+                epsilon = 1e-5
+            );
+        }
+    }
+
+    #[test]
+    fn curve_mapped() {
+        let curve = pop_curve().mapped(|v| 1.0 - v);
+        assert_relative_eq!(curve.sample(0.0), 1.0);
+        assert_relative_eq!(curve.sample(0.25), 0.0);
+        assert_relative_eq!(curve.sample(1.0), 0.5);
+    }
+
+    #[test]
+    fn curve_sample_range_matches_sample() {
+        let mut curve = Curve::new(vec![
+            CurvePoint::new(0.0, 0.0),
+            CurvePoint::new(1.0, 0.3),
+            CurvePoint::new(0.5, 1.0),
+        ]);
+        let pcts: Vec<f32> = (0..=10u8).map(|i| f32::from(i) / 10.0).collect();
+
+        let baked = curve.sample_range(11);
+        assert_eq!(baked.len(), 11);
+        for (value, pct) in baked.iter().zip(&pcts) {
+            assert_relative_eq!(*value, curve.sample(*pct), epsilon = 1e-5);
+        }
+        for (value, pct) in curve.sample_many(&pcts).iter().zip(&pcts) {
+            assert_relative_eq!(*value, curve.sample(*pct), epsilon = 1e-5);
+        }
+
+        assert!(curve.sample_range(0).is_empty());
+        assert_eq!(curve.sample_range(1), vec![0.0]);
+    }
+
+    #[test]
+    fn curve_bakes_gradient_image() {
+        let mut curve = Curve::new(vec![
+            CurvePoint::new(Color::BLACK, 0.0),
+            CurvePoint::new(Color::WHITE.with_alpha(0.0), 1.0),
+        ]);
+        let image = curve.bake_to_image(3);
+        assert_eq!(image.width(), 3);
+        assert_eq!(image.height(), 1);
+
+        let pixel = |index: usize| &image.data[index * 4..index * 4 + 4];
+        assert_eq!(pixel(0), &[0, 0, 0, 255]);
+        let midpoint = pixel(1);
+        assert_eq!(midpoint[0], midpoint[1]);
+        assert_eq!(midpoint[1], midpoint[2]);
+        assert!(midpoint[0] > 0 && midpoint[0] < 255);
+        // Alpha is stored straight, the transparent end keeps its white color.
+        assert_eq!(pixel(2), &[255, 255, 255, 0]);
+    }
 
     #[test]
     fn jittered_value_from_range() {
@@ -1197,4 +2345,182 @@ mod tests {
         assert_relative_eq!(curve.sample(0.75), 0.5);
         assert_relative_eq!(curve.sample(1.0), 0.0);
     }
+
+    #[test]
+    fn flicker_without_intensity_is_disabled() {
+        let flicker = FlickerModifier::new(30.0, 0.0);
+        for step in 0..100_u16 {
+            assert_relative_eq!(
+                flicker.sample(u32::from(step) * 7, f32::from(step) * 0.1),
+                1.0
+            );
+        }
+    }
+
+    fn quad_mesh() -> Mesh {
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![
+                [0.0, 0.0, 0.0],
+                [2.0, 0.0, 0.0],
+                [2.0, 1.0, 0.0],
+                [0.0, 1.0, 0.0],
+            ],
+        )
+        .with_inserted_indices(Indices::U16(vec![0, 1, 2, 0, 2, 3]))
+    }
+
+    #[test]
+    fn mesh_surface_samples_lie_on_mesh() {
+        let sampler = MeshSurfaceSampler::new(&quad_mesh()).unwrap();
+        let mut rng = SmallRng::seed_from_u64(7);
+
+        for _ in 0..500 {
+            let transform = sampler.sample(&mut rng);
+            let position = transform.translation;
+            assert!((0.0..=2.0).contains(&position.x));
+            assert!((0.0..=1.0).contains(&position.y));
+            assert_relative_eq!(position.z, 0.0);
+            // Particles move along the local X axis, which follows the face normal.
+            assert!((transform.rotation * Vec3::X).abs_diff_eq(Vec3::Z, 1e-5));
+        }
+    }
+
+    #[test]
+    fn mesh_surface_rejects_non_triangle_lists() {
+        let mesh = Mesh::new(PrimitiveTopology::LineList, RenderAssetUsages::default())
+            .with_inserted_attribute(
+                Mesh::ATTRIBUTE_POSITION,
+                vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0]],
+            );
+        assert!(MeshSurfaceSampler::new(&mesh).is_none());
+    }
+
+    #[test]
+    fn normalized_distance_spans_emitter() {
+        let circle = EmitterShape::circle(JitteredValue::jittered(8.0, -4.0..2.0));
+        assert_relative_eq!(circle.normalized_distance(Vec3::ZERO).unwrap(), 0.0);
+        assert_relative_eq!(
+            circle
+                .normalized_distance(Vec3::new(0.0, 5.0, 0.0))
+                .unwrap(),
+            0.5
+        );
+        assert_relative_eq!(
+            circle
+                .normalized_distance(Vec3::new(10.0, 0.0, 0.0))
+                .unwrap(),
+            1.0
+        );
+
+        let line = EmitterShape::line(10.0, 0.0);
+        assert_relative_eq!(
+            line.normalized_distance(Vec3::new(0.0, -5.0, 0.0)).unwrap(),
+            1.0
+        );
+        assert_relative_eq!(
+            line.normalized_distance(Vec3::new(0.0, 2.5, 0.0)).unwrap(),
+            0.5
+        );
+
+        assert_relative_eq!(
+            EmitterShape::circle(0.0)
+                .normalized_distance(Vec3::ZERO)
+                .unwrap(),
+            0.0
+        );
+    }
+
+    #[test]
+    fn line_emission_direction() {
+        let mut rng = SmallRng::seed_from_u64(3);
+        let angle = 0.7;
+        let line_direction = Quat::from_rotation_z(angle) * Vec3::Y;
+
+        let curtain = EmitterShape::line(10.0, angle);
+        let conveyor = EmitterShape::Line(Line::new(10.0, angle).with_emit_perpendicular(false));
+        for _ in 0..20 {
+            let sample = curtain.sample(&mut rng);
+            assert_relative_eq!(
+                sample.translation.cross(line_direction).length(),
+                0.0,
+                epsilon = 1e-4
+            );
+            assert_relative_eq!(
+                (sample.rotation * Vec3::X).dot(line_direction),
+                0.0,
+                epsilon = 1e-5
+            );
+
+            let sample = conveyor.sample(&mut rng);
+            assert_relative_eq!(
+                sample.translation.cross(line_direction).length(),
+                0.0,
+                epsilon = 1e-4
+            );
+            assert!((sample.rotation * Vec3::X).abs_diff_eq(line_direction, 1e-5));
+        }
+    }
+
+    #[test]
+    fn color_blend_ops() {
+        let base = LinearRgba::new(0.5, 0.25, 1.0, 1.0).into();
+        let layer = LinearRgba::new(0.5, 0.5, 0.5, 0.5).into();
+
+        let multiplied = ColorBlendOp::Multiply.blend(base, layer).to_linear();
+        assert_eq!(multiplied, LinearRgba::new(0.25, 0.125, 0.5, 0.5));
+
+        let screened = ColorBlendOp::Screen.blend(base, layer).to_linear();
+        assert_eq!(screened, LinearRgba::new(0.75, 0.625, 1.0, 1.0));
+
+        let added = ColorBlendOp::Add.blend(base, layer).to_linear();
+        assert_eq!(added, LinearRgba::new(1.0, 0.75, 1.0, 1.0));
+    }
+
+    #[test]
+    fn velocity_constraint_zeroes_locked_axes() {
+        let velocity = Vec3::new(1.0, 2.0, 3.0);
+
+        assert_eq!(VelocityConstraint::default().apply(velocity), velocity);
+        assert_eq!(
+            VelocityConstraint {
+                lock_x: true,
+                lock_y: false,
+                lock_z: true,
+            }
+            .apply(velocity),
+            Vec3::new(0.0, 2.0, 0.0)
+        );
+        assert_eq!(
+            VelocityConstraint {
+                lock_x: true,
+                lock_y: true,
+                lock_z: true,
+            }
+            .apply(velocity),
+            Vec3::ZERO
+        );
+    }
+
+    #[test]
+    fn color_gradient_sorts_points() {
+        let gradient =
+            ColorOverTime::gradient([(1.0, Srgba::BLUE), (0.0, Srgba::RED), (0.5, Srgba::GREEN)]);
+
+        assert_eq!(gradient.at_lifetime_pct(0.0), Srgba::RED.into());
+        assert_eq!(gradient.at_lifetime_pct(0.5), Srgba::GREEN.into());
+        assert_eq!(gradient.at_lifetime_pct(1.0), Srgba::BLUE.into());
+    }
+
+    #[test]
+    fn two_point_color_gradient_is_a_lerp() {
+        let gradient =
+            ColorOverTime::gradient([(0.0, LinearRgba::BLACK), (1.0, LinearRgba::WHITE)]);
+
+        assert!(matches!(gradient, ColorOverTime::Lerp(_)));
+    }
 }